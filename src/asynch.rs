@@ -0,0 +1,795 @@
+//! Async driver, built on `display_interface::AsyncWriteOnlyDataCommand` and `embedded_hal_async`.
+//!
+//! [`ST7796Async`] mirrors the blocking [`ST7796`](crate::ST7796) API: the same
+//! methods, driver states, [`InitConfig`] and [`Error`], with `.await` on the
+//! calls that talk to the display. Both drivers send the same command
+//! sequences and keep the same cached state. The reset pin is an
+//! `embedded_hal` 1.0 output pin and the backlight any [`Backlight`], such as a
+//! `GpioBacklight::new(Eh1Pin(pin))` or a [`PwmBacklight`](crate::PwmBacklight).
+//!
+//! [`DcsBacklight`](crate::DcsBacklight) borrows the blocking driver; with
+//! the async driver the display brightness is set by writing
+//! [`BrightnessControl`](crate::BrightnessControl) and
+//! [`Brightness`](crate::Brightness) with `write_register`.
+
+use crate::backlight::fade_steps;
+use crate::instruction::{Command, Instruction};
+use crate::raw::CachedState;
+use crate::sequence::{self, Params, Step};
+use crate::{
+    pixel_count, rgb565_to_bytes, Backlight, BacklightState, Error, InitConfig, Madctl, NoPin, PanelGeometry,
+    PixelFormat, PowerState, Ready, Sleeping, Uninit,
+};
+use core::iter::{once, repeat};
+use core::marker::PhantomData;
+use display_interface::DisplayError;
+use display_interface_async::AsyncWriteOnlyDataCommand;
+use display_interface_async::DataFormat::{U16BEIter, U16BE, U8Iter, U8};
+use embedded_hal_1::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+
+///
+/// ST7796S driver to connect with TFT Display, using an async display interface.
+///
+/// `S` is the driver state, see [`state`](crate::state): `new` returns an
+/// [`Uninit`] driver, only a [`Ready`] driver draws.
+///
+pub struct ST7796Async<DI, RST, BL, S = Ready>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    // Display Interface.
+    di: DI,
+    // Reset Pin.
+    rst: Option<RST>,
+    // Backlight.
+    bl: Option<BL>,
+    // display state, kept in step with the commands sent.
+    cache: CachedState,
+    // Uninit, Ready or Sleeping.
+    state: PhantomData<S>,
+}
+
+impl<DI, RST, BL, RstE, BlE> ST7796Async<DI, RST, BL, Uninit>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Creates a new ST7796 driver instance
+    ///
+    /// # Arguments.
+    ///
+    /// * `di` - Async Display Interface to communicate with display.
+    /// * `rst` - Display hard reset pin.
+    /// * `bl` - backlight, such as a [`GpioBacklight`](crate::GpioBacklight).
+    /// * `size_x` - x axis resolution of the display in pixels, in portrait orientation.
+    /// * `size_y` - y axis resolution of the display in pixels, in portrait orientation.
+    ///
+    pub fn new(di: DI, rst: Option<RST>, bl: Option<BL>, size_x: u16, size_y: u16) -> Self {
+        Self::with_geometry(di, rst, bl, PanelGeometry::new(size_x, size_y))
+    }
+
+    ///
+    /// Creates a new ST7796 driver instance for a panel that does not
+    /// cover the whole 320x480 GRAM.
+    ///
+    /// # Arguments.
+    ///
+    /// * `di` - Async Display Interface to communicate with display.
+    /// * `rst` - Display hard reset pin.
+    /// * `bl` - backlight, such as a [`GpioBacklight`](crate::GpioBacklight).
+    /// * `geometry` - visible size and GRAM offset of the panel.
+    ///
+    pub fn with_geometry(di: DI, rst: Option<RST>, bl: Option<BL>, geometry: PanelGeometry) -> Self {
+        Self {
+            di, rst, bl,
+            cache: CachedState::new(geometry),
+            state: PhantomData,
+        }
+    }
+
+    ///
+    /// Runs commands to intialize the display, using [`InitConfig::default`],
+    /// returning the ready driver.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay_source` - mutable reference to an async delay provider.
+    ///
+    pub async fn init(
+        self,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<ST7796Async<DI, RST, BL, Ready>, (Self, Error<RstE, BlE>)> {
+        self.init_with(&InitConfig::default(), delay_source).await
+    }
+
+    ///
+    /// Runs the bring-up sequence described by `config`, returning the ready
+    /// driver, or the uninitialized one with the error, as
+    /// [`ST7796::init_with`](crate::ST7796::init_with) does.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - the init sequence to run.
+    /// * `delay_source` - mutable reference to an async delay provider.
+    ///
+    pub async fn init_with(
+        mut self,
+        config: &InitConfig,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<ST7796Async<DI, RST, BL, Ready>, (Self, Error<RstE, BlE>)> {
+        if !config.is_valid(&self.cache.geometry) {
+            return Err((self, Error::InvalidConfig));
+        }
+
+        match self.bring_up(config, delay_source).await {
+            Ok(()) => Ok(self.into_state()),
+            Err(error) => Err((self, error)),
+        }
+    }
+
+    ///
+    /// Performs a hard reset using the RST pin sequence
+    ///
+    /// # Arguments
+    ///
+    /// * `delay_source` - mutable reference to an async delay provider
+    ///
+    pub async fn hard_reset(&mut self, delay_source: &mut impl DelayNs) -> Result<(), Error<RstE, BlE>> {
+        if let Some(rst) = self.rst.as_mut() {
+            rst.set_high().map_err(Error::Reset)?;
+            delay_source.delay_us(10).await; // ensure the pin change will get registered
+            rst.set_low().map_err(Error::Reset)?;
+            delay_source.delay_us(10).await; // ensure the pin change will get registered
+            rst.set_high().map_err(Error::Reset)?;
+            delay_source.delay_us(10).await; // ensure the pin change will get registered
+            self.cache.reset(true);
+        }
+
+        Ok(())
+    }
+
+    // --- Private Functions --- //
+
+    /// Private method:Resets the display and runs the init sequence of `config`.
+    async fn bring_up(&mut self, config: &InitConfig, delay_source: &mut impl DelayNs) -> Result<(), Error<RstE, BlE>> {
+        let hard_reset = self.rst.is_some();
+        self.hard_reset(delay_source).await?;
+        self.run(sequence::init(config, hard_reset), delay_source).await?;
+        if let (Some(bl), Some(brightness)) = (self.bl.as_mut(), config.backlight) {
+            bl.set_brightness(brightness).map_err(Error::Backlight)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, RST, BL, S, RstE, BlE> ST7796Async<DI, RST, BL, S>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Method to set the state of BacklightState: full brightness or off.
+    ///
+    pub async fn set_backlight(
+        &mut self, state: BacklightState,
+        delay_source: &mut impl DelayNs
+    ) -> Result<(), Error<RstE, BlE>> {
+        if let Some(bl) = self.bl.as_mut() {
+            match state {
+                BacklightState::ON => bl.set_brightness(u8::MAX).map_err(Error::Backlight)?,
+                BacklightState::OFF => bl.set_brightness(0).map_err(Error::Backlight)?,
+            }
+            delay_source.delay_us(10).await;
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the backlight, for brightness changes.
+    ///
+    pub fn backlight(&mut self) -> Option<&mut BL> {
+        self.bl.as_mut()
+    }
+
+    ///
+    /// Changes the backlight brightness to `target` one step at a time, as
+    /// [`Backlight::fade_to`] does, waiting with the async delay provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - brightness at the end of the fade.
+    /// * `duration_us` - duration of the fade.
+    /// * `delay_source` - mutable reference to an async delay provider.
+    ///
+    pub async fn fade_backlight(
+        &mut self,
+        target: u8,
+        duration_us: u32,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<(), Error<RstE, BlE>> {
+        if let Some(bl) = self.bl.as_mut() {
+            let (step_us, steps) = fade_steps(bl.brightness(), target, duration_us);
+            for brightness in steps {
+                delay_source.delay_us(step_us).await;
+                bl.set_brightness(brightness).map_err(Error::Backlight)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the current sleep and display state.
+    ///
+    pub fn power_state(&self) -> PowerState {
+        self.cache.power_state
+    }
+
+    ///
+    /// Returns the panel geometry.
+    ///
+    pub fn geometry(&self) -> PanelGeometry {
+        self.cache.geometry
+    }
+
+    ///
+    /// Returns the current MADCTL setting.
+    ///
+    pub fn orientation(&self) -> Madctl {
+        self.cache.madctl
+    }
+
+    ///
+    /// Returns the current interface pixel format.
+    ///
+    pub fn pixel_format(&self) -> PixelFormat {
+        self.cache.pixel_format
+    }
+
+    ///
+    /// Returns true if display inversion is on.
+    ///
+    pub fn is_inverted(&self) -> bool {
+        self.cache.inverted
+    }
+
+    ///
+    /// Release resources allocated to this driver back.
+    /// This returns the display interface and the RST pin; deconstructing the driver.
+    ///
+    pub fn release(self) -> (DI, Option<RST>, Option<BL>) {
+        (self.di, self.rst, self.bl)
+    }
+
+    // --- Private Functions --- //
+
+    /// Private method:Writing Data utilising the `send_commands` method of display_interface crate.
+    async fn write_command(&mut self, command: Command) -> Result<(), Error<RstE, BlE>> {
+        self.di
+            .send_commands(U8Iter(&mut once(command as u8)))
+            .await
+            .map_err(interface_error)
+    }
+
+    /// Private method:Writing Data utilising the `send_data` method of display_interface crate.
+    /// Nothing is sent for a command without parameters.
+    async fn write_data(&mut self, data: &[u8]) -> Result<(), Error<RstE, BlE>> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        self.di
+            .send_data(U8Iter(&mut data.iter().cloned()))
+            .await
+            .map_err(interface_error)
+    }
+
+    /// Private method:Sends commands built by `sequence`, keeping the cached state in step.
+    async fn write_all<'a>(
+        &mut self,
+        writes: impl IntoIterator<Item = (Command, Params<'a>)>,
+    ) -> Result<(), Error<RstE, BlE>> {
+        for (command, params) in writes {
+            self.write_command(command).await?;
+            self.write_data(params.as_slice()).await?;
+            self.cache.sync(command, params.as_slice());
+        }
+
+        Ok(())
+    }
+
+    /// Private method:Runs a sequence built by `sequence`, waiting where it says.
+    async fn run<'a>(
+        &mut self,
+        steps: impl IntoIterator<Item = Step<'a>>,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<(), Error<RstE, BlE>> {
+        for step in steps {
+            match step {
+                Step::Write(command, params) => {
+                    self.write_command(command).await?;
+                    self.write_data(params.as_slice()).await?;
+                }
+                Step::Wait(us) => delay_source.delay_us(us).await,
+            }
+            self.cache.apply(&step);
+        }
+
+        Ok(())
+    }
+
+    /// Private method:Changes the state type parameter, keeping all fields.
+    fn into_state<T>(self) -> ST7796Async<DI, RST, BL, T> {
+        ST7796Async {
+            di: self.di,
+            rst: self.rst,
+            bl: self.bl,
+            cache: self.cache,
+            state: PhantomData,
+        }
+    }
+}
+
+impl<DI, RST, BL, RstE, BlE> ST7796Async<DI, RST, BL, Sleeping>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Leaves sleep mode (SLPOUT), returning the ready driver, or the
+    /// sleeping one with the error. Waits as [`ST7796::wake`](crate::ST7796::wake) does.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay_source` - mutable reference to an async delay provider.
+    ///
+    pub async fn wake(
+        mut self,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<ST7796Async<DI, RST, BL, Ready>, (Self, Error<RstE, BlE>)> {
+        match self.run(sequence::wake(), delay_source).await {
+            Ok(()) => Ok(self.into_state()),
+            Err(error) => Err((self, error)),
+        }
+    }
+}
+
+impl<DI, RST, BL, RstE, BlE> ST7796Async<DI, RST, BL, Ready>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Enters sleep mode (SLPIN), returning the sleeping driver, or the
+    /// ready one with the error. Waits as [`ST7796::sleep`](crate::ST7796::sleep) does.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay_source` - mutable reference to an async delay provider.
+    ///
+    pub async fn sleep(
+        self,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<ST7796Async<DI, RST, BL, Sleeping>, (Self, Error<RstE, BlE>)> {
+        self.sleep_after(0, delay_source).await
+    }
+
+    ///
+    /// Enters sleep mode (SLPIN), as [`ST7796::sleep_after`](crate::ST7796::sleep_after) does.
+    ///
+    /// # Arguments
+    ///
+    /// * `elapsed_us` - time passed since `init` or `wake` returned.
+    /// * `delay_source` - mutable reference to an async delay provider.
+    ///
+    pub async fn sleep_after(
+        mut self,
+        elapsed_us: u32,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<ST7796Async<DI, RST, BL, Sleeping>, (Self, Error<RstE, BlE>)> {
+        if !self.cache.power_state.sleeping {
+            let wait_us = self.cache.sleep_in_wait_us.saturating_sub(elapsed_us);
+            if let Err(error) = self.run(sequence::sleep(wait_us), delay_source).await {
+                return Err((self, error));
+            }
+        }
+
+        Ok(self.into_state())
+    }
+
+    ///
+    /// Turns the display output off (DISPOFF); the frame memory is kept.
+    ///
+    pub async fn display_off(&mut self) -> Result<(), Error<RstE, BlE>> {
+        self.write_command(Command::DISPOFF).await?;
+        self.cache.power_state.display_on = false;
+
+        Ok(())
+    }
+
+    ///
+    /// Turns the display output on (DISPON).
+    ///
+    pub async fn display_on(&mut self) -> Result<(), Error<RstE, BlE>> {
+        self.write_command(Command::DISPON).await?;
+        self.cache.power_state.display_on = true;
+
+        Ok(())
+    }
+
+    ///
+    /// Sets the display orientation, mirroring and color order (MADCTL).
+    ///
+    pub async fn set_orientation(&mut self, orientation: impl Into<Madctl>) -> Result<(), Error<RstE, BlE>> {
+        let madctl = orientation.into();
+        self.write_register(&madctl).await?;
+        self.cache.madctl = madctl;
+
+        Ok(())
+    }
+
+    ///
+    /// Sets the interface pixel format.
+    /// Rgb565 colors are expanded to 3 bytes when an 18 or 24 bit format is active.
+    ///
+    pub async fn set_pixel_format(&mut self, pixel_format: PixelFormat) -> Result<(), Error<RstE, BlE>> {
+        self.write_register(&pixel_format).await?;
+        self.cache.pixel_format = pixel_format;
+
+        Ok(())
+    }
+
+    ///
+    /// Turns display inversion on (INVON) or off (INVOFF).
+    ///
+    pub async fn set_inverted(&mut self, inverted: bool) -> Result<(), Error<RstE, BlE>> {
+        self.write_command(if inverted { Command::INVON } else { Command::INVOFF }).await?;
+        self.cache.inverted = inverted;
+
+        Ok(())
+    }
+
+    ///
+    /// Sets a pixel color at the given coords.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X  coordinate.
+    /// * `y` - Y  coordinate.
+    /// * `color` - the Rgb565 color value
+    ///
+    pub async fn set_pixel(&mut self, x: u16, y: u16, color: u16) -> Result<(), Error<RstE, BlE>> {
+        self.set_pixels(x, y, x, y, once(color)).await
+    }
+
+    ///
+    /// Sets pixel colors in given rectangle bounds.
+    /// Fails with [`Error::OutOfBounds`] unless the rectangle is inside the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `colors` - anything that can provide `IntoIterator<Item = u16>` to iterate over pixel data
+    ///
+    pub async fn set_pixels<T>(
+        &mut self,
+        sx: u16, sy: u16,
+        ex: u16, ey: u16,
+        colors: T,
+    ) -> Result<(), Error<RstE, BlE>>
+    where
+        T: IntoIterator<Item = u16>,
+    {
+        self.start_pixels(sx, sy, ex, ey).await?;
+        let result = match self.cache.pixel_format {
+            PixelFormat::Rgb565 => self.di.send_data(U16BEIter(&mut colors.into_iter())).await,
+            PixelFormat::Rgb666 | PixelFormat::Rgb888 => {
+                self.di
+                    .send_data(U8Iter(&mut colors.into_iter().flat_map(rgb565_to_bytes)))
+                    .await
+            }
+        };
+
+        result.map_err(interface_error)
+    }
+
+    ///
+    /// Sets pixel colors in given rectangle bounds from a buffer, passed to the
+    /// display interface as one slice, as [`ST7796::write_pixels`](crate::ST7796::write_pixels)
+    /// does. The interface converts the colors to big endian in place, so on
+    /// little endian targets the buffer holds byte-swapped colors afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `colors` - Rgb565 colors, row by row
+    ///
+    pub async fn write_pixels(
+        &mut self,
+        sx: u16, sy: u16,
+        ex: u16, ey: u16,
+        colors: &mut [u16],
+    ) -> Result<(), Error<RstE, BlE>> {
+        if colors.len() != pixel_count(sx, sy, ex, ey) {
+            return Err(Error::OutOfBounds);
+        }
+
+        self.start_pixels(sx, sy, ex, ey).await?;
+        let result = match self.cache.pixel_format {
+            PixelFormat::Rgb565 => self.di.send_data(U16BE(colors)).await,
+            PixelFormat::Rgb666 | PixelFormat::Rgb888 => {
+                self.di
+                    .send_data(U8Iter(&mut colors.iter().copied().flat_map(rgb565_to_bytes)))
+                    .await
+            }
+        };
+
+        result.map_err(interface_error)
+    }
+
+    ///
+    /// Sets pixel colors in given rectangle bounds from bytes already in the
+    /// interface pixel format, as [`ST7796::write_pixels_be`](crate::ST7796::write_pixels_be) does.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `data` - pixel bytes, row by row
+    ///
+    pub async fn write_pixels_be(
+        &mut self,
+        sx: u16, sy: u16,
+        ex: u16, ey: u16,
+        data: &[u8],
+    ) -> Result<(), Error<RstE, BlE>> {
+        if data.len() != pixel_count(sx, sy, ex, ey) * self.cache.pixel_format.bytes_per_pixel() {
+            return Err(Error::OutOfBounds);
+        }
+
+        self.start_pixels(sx, sy, ex, ey).await?;
+        self.di.send_data(U8(data)).await.map_err(interface_error)
+    }
+
+    ///
+    /// Fills the given rectangle bounds with one color.
+    /// Fails with [`Error::OutOfBounds`] unless the rectangle is inside the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `color` - the Rgb565 color value
+    ///
+    pub async fn fill(&mut self, sx: u16, sy: u16, ex: u16, ey: u16, color: u16) -> Result<(), Error<RstE, BlE>> {
+        self.set_pixels(sx, sy, ex, ey, repeat(color).take(pixel_count(sx, sy, ex, ey))).await
+    }
+
+    ///
+    /// Writes a whole frame of Rgb565 colors, row by row in the current orientation.
+    /// Fails with [`Error::OutOfBounds`] unless `frame` holds exactly one color per pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - frame buffer of `width * height` colors.
+    ///
+    pub async fn flush(&mut self, frame: &[u16]) -> Result<(), Error<RstE, BlE>> {
+        let (width, height) = self.cache.geometry.size(self.cache.madctl);
+        if frame.len() != usize::from(width) * usize::from(height) {
+            return Err(Error::OutOfBounds);
+        }
+
+        self.set_pixels(0, 0, width - 1, height - 1, frame.iter().copied()).await
+    }
+
+    ///
+    /// Writes a typed register, enabling Command Table 2 with CSCON when the register needs it.
+    /// The cached state is updated as for [`ST7796::write_register`](crate::ST7796::write_register).
+    ///
+    pub async fn write_register<I: Instruction>(&mut self, register: &I) -> Result<(), Error<RstE, BlE>> {
+        self.write_all(sequence::register(register)).await
+    }
+
+    // --- Private Functions --- //
+
+    /// Private method:Sets the address window, shifted by the panel offset, and starts writing
+    /// the frame memory (RAMWR). Fails with `OutOfBounds` unless the window is inside the visible area.
+    async fn start_pixels(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), Error<RstE, BlE>> {
+        let writes = sequence::address_window(&self.cache, sx, sy, ex, ey).ok_or(Error::OutOfBounds)?;
+        self.write_all(writes).await?;
+        self.write_command(Command::RAMWR).await
+    }
+}
+
+impl<DI, BL, BlE> ST7796Async<DI, NoPin, BL, Uninit>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Creates a new ST7796 driver instance for a display without a reset pin.
+    ///
+    /// # Arguments.
+    ///
+    /// * `di` - Async Display Interface to communicate with display.
+    /// * `bl` - backlight, such as a [`GpioBacklight`](crate::GpioBacklight).
+    /// * `size_x` - x axis resolution of the display in pixels, in portrait orientation.
+    /// * `size_y` - y axis resolution of the display in pixels, in portrait orientation.
+    ///
+    pub fn new_without_reset(di: DI, bl: Option<BL>, size_x: u16, size_y: u16) -> Self {
+        Self::new(di, None, bl, size_x, size_y)
+    }
+}
+
+impl<DI, RST, RstE> ST7796Async<DI, RST, NoPin, Uninit>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+{
+    ///
+    /// Creates a new ST7796 driver instance for a display without a backlight pin.
+    ///
+    /// # Arguments.
+    ///
+    /// * `di` - Async Display Interface to communicate with display.
+    /// * `rst` - Display hard reset pin.
+    /// * `size_x` - x axis resolution of the display in pixels, in portrait orientation.
+    /// * `size_y` - y axis resolution of the display in pixels, in portrait orientation.
+    ///
+    pub fn new_without_backlight(di: DI, rst: Option<RST>, size_x: u16, size_y: u16) -> Self {
+        Self::new(di, rst, None, size_x, size_y)
+    }
+}
+
+impl<DI> ST7796Async<DI, NoPin, NoPin, Uninit>
+where
+    DI: AsyncWriteOnlyDataCommand,
+{
+    ///
+    /// Creates a new ST7796 driver instance for a display without reset and backlight pins.
+    ///
+    /// # Arguments.
+    ///
+    /// * `di` - Async Display Interface to communicate with display.
+    /// * `size_x` - x axis resolution of the display in pixels, in portrait orientation.
+    /// * `size_y` - y axis resolution of the display in pixels, in portrait orientation.
+    ///
+    pub fn new_without_pins(di: DI, size_x: u16, size_y: u16) -> Self {
+        Self::new(di, None, None, size_x, size_y)
+    }
+}
+
+/// Maps an async display interface error to the [`DisplayError`] of [`Error::Interface`].
+fn interface_error<RstE, BlE>(error: display_interface_async::DisplayError) -> Error<RstE, BlE> {
+    use display_interface_async::DisplayError as E;
+
+    Error::Interface(match error {
+        E::InvalidFormatError => DisplayError::InvalidFormatError,
+        E::DCError => DisplayError::DCError,
+        E::CSError => DisplayError::CSError,
+        E::DataFormatNotImplemented => DisplayError::DataFormatNotImplemented,
+        E::RSError => DisplayError::RSError,
+        E::OutOfBoundsError => DisplayError::OutOfBoundsError,
+        _ => DisplayError::BusWriteError,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::mock::{block_on, MockDelay, MockInterface, MockPin};
+    use crate::{Gamma, ST7796};
+
+    /// Returns an initialized 320x480 async driver without pins, with nothing recorded yet.
+    fn ready_display() -> ST7796Async<MockInterface, NoPin, NoPin> {
+        let mut display = block_on(
+            ST7796Async::new_without_pins(MockInterface::default(), 320, 480).init(&mut MockDelay::default()),
+        )
+        .unwrap_or_else(|_| panic!("init failed"));
+        display.di = MockInterface::default();
+        display
+    }
+
+    #[test]
+    fn init_matches_the_blocking_driver() {
+        let config = InitConfig::default().with_gamma(Gamma::SITRONIX_REFERENCE);
+        let mut delay = MockDelay::default();
+        let display = block_on(
+            ST7796Async::new(MockInterface::default(), Some(MockPin::default()), None::<NoPin>, 320, 480)
+                .init_with(&config, &mut delay),
+        )
+        .unwrap_or_else(|_| panic!("init failed"));
+
+        let mut blocking_delay = MockDelay::default();
+        let blocking = ST7796::new(MockInterface::default(), Some(MockPin::default()), None::<NoPin>, 320, 480)
+            .init_with(&config, &mut blocking_delay)
+            .unwrap_or_else(|_| panic!("init failed"));
+
+        assert_eq!(display.di.commands, blocking.di.commands);
+        assert_eq!(delay.total_us, blocking_delay.total_us);
+        assert_eq!(display.power_state(), PowerState { sleeping: false, display_on: true });
+        let (_, rst, _) = display.release();
+        assert_eq!(rst.unwrap().levels, [true, false, true]);
+    }
+
+    #[test]
+    fn init_rejects_invalid_geometry() {
+        let geometry = PanelGeometry::new(320, 480).with_offset(0, 1);
+        let display = ST7796Async::<_, NoPin, NoPin, _>::with_geometry(MockInterface::default(), None, None, geometry);
+        let result = block_on(display.init(&mut MockDelay::default()));
+
+        assert!(matches!(result, Err((_, Error::InvalidConfig))));
+    }
+
+    #[test]
+    fn sleep_after_counts_elapsed_time() {
+        let mut delay = MockDelay::default();
+        // init waited 40ms since SLPOUT
+        let display = block_on(ready_display().sleep_after(70_000, &mut delay)).unwrap_or_else(|_| panic!("sleep failed"));
+        assert_eq!(delay.total_us, 10_000 + 5_000);
+
+        let mut delay = MockDelay::default();
+        let display = block_on(display.wake(&mut delay)).unwrap_or_else(|_| panic!("wake failed"));
+        let display = block_on(display.sleep_after(1_000_000, &mut delay)).unwrap_or_else(|_| panic!("sleep failed"));
+        assert_eq!(delay.total_us, 5_000 + 5_000);
+        assert_eq!(display.di.command_bytes(), [Command::SLPIN, Command::SLPOUT, Command::SLPIN].map(|command| command as u8));
+    }
+
+    #[test]
+    fn command2_registers_are_enabled_with_cscon() {
+        let mut display = ready_display();
+        block_on(display.write_register(&crate::Vcom::new(0x25).unwrap())).unwrap();
+
+        let commands = [Command::CSCON, Command::CSCON, Command::VMCTR1, Command::CSCON, Command::CSCON];
+        assert_eq!(display.di.command_bytes(), commands.map(|command| command as u8));
+        assert_eq!(display.di.params(Command::VMCTR1), Some(&[0x25][..]));
+    }
+
+    #[test]
+    fn write_pixels_sends_one_big_endian_slice() {
+        let mut display = ready_display();
+        let mut colors: std::vec::Vec<u16> = (0..300).map(|i| 0x1200 | i).collect();
+        let expected: std::vec::Vec<u8> = colors.iter().flat_map(|color| color.to_be_bytes()).collect();
+        block_on(display.write_pixels(0, 0, 29, 9, &mut colors)).unwrap();
+
+        assert_eq!(display.di.params(Command::RAMWR), Some(expected.as_slice()));
+        // CASET, RASET and the colors
+        assert_eq!(display.di.data_writes, 3);
+        assert!(matches!(block_on(display.write_pixels(0, 0, 1, 1, &mut [0; 3])), Err(Error::OutOfBounds)));
+    }
+
+    #[test]
+    fn set_orientation_moves_the_address_window() {
+        let geometry = PanelGeometry::new(200, 400).with_offset(10, 20);
+        let display = ST7796Async::<_, NoPin, NoPin, _>::with_geometry(MockInterface::default(), None, None, geometry);
+        let mut display = block_on(display.init(&mut MockDelay::default())).unwrap_or_else(|_| panic!("init failed"));
+        block_on(display.set_orientation(crate::Rotation::Deg90)).unwrap();
+        display.di = MockInterface::default();
+        block_on(display.set_pixel(0, 0, 0xFFFF)).unwrap();
+
+        assert_eq!(display.di.params(Command::CASET), Some(&[0, 20, 0, 20][..]));
+        assert_eq!(display.di.params(Command::RASET), Some(&[0, 110, 0, 110][..]));
+    }
+}
@@ -0,0 +1,244 @@
+//! Backlight control: a GPIO pin, a PWM channel or the display brightness register (WRDISBV).
+
+use crate::brightness::{Brightness, BrightnessControl};
+use crate::{Error, NoPin, ST7796};
+use core::convert::Infallible;
+use display_interface::WriteOnlyDataCommand;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::digital::v2::OutputPin;
+use embedded_hal::PwmPin;
+
+///
+/// Backlight with a brightness from 0 (off) to 255 (full).
+///
+pub trait Backlight {
+    /// Error raised while setting the brightness.
+    type Error;
+
+    ///
+    /// Sets the brightness, 0 is off and 255 full brightness.
+    ///
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Self::Error>;
+
+    ///
+    /// Returns the brightness last set.
+    ///
+    fn brightness(&self) -> u8;
+
+    ///
+    /// Changes the brightness to `target` one step at a time, spreading the
+    /// steps evenly over `duration_us`.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - brightness at the end of the fade.
+    /// * `duration_us` - duration of the fade.
+    /// * `delay_source` - mutable reference to a delay provider.
+    ///
+    fn fade_to(&mut self, target: u8, duration_us: u32, delay_source: &mut impl DelayUs<u32>) -> Result<(), Self::Error> {
+        let (step_us, steps) = fade_steps(self.brightness(), target, duration_us);
+        for brightness in steps {
+            delay_source.delay_us(step_us);
+            self.set_brightness(brightness)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the time between fade steps and the brightness of each step from `start` to `target`.
+pub(crate) fn fade_steps(start: u8, target: u8, duration_us: u32) -> (u32, impl Iterator<Item = u8>) {
+    let steps = start.abs_diff(target);
+    let step_us = duration_us.checked_div(u32::from(steps)).unwrap_or(0);
+    let brightness = (1..=steps).map(move |step| if target > start { start + step } else { start - step });
+
+    (step_us, brightness)
+}
+
+impl Backlight for NoPin {
+    type Error = Infallible;
+
+    fn set_brightness(&mut self, _brightness: u8) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn brightness(&self) -> u8 {
+        0
+    }
+}
+
+///
+/// Backlight switched by an output pin, on at any brightness above 0.
+///
+pub struct GpioBacklight<P> {
+    pin: P,
+    brightness: u8,
+}
+
+impl<P: OutputPin> GpioBacklight<P> {
+    ///
+    /// Creates the backlight from an active high pin, assumed off.
+    ///
+    pub fn new(pin: P) -> Self {
+        Self { pin, brightness: 0 }
+    }
+
+    ///
+    /// Returns the pin.
+    ///
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+impl<P: OutputPin> Backlight for GpioBacklight<P> {
+    type Error = P::Error;
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), P::Error> {
+        if brightness > 0 {
+            self.pin.set_high()?;
+        } else {
+            self.pin.set_low()?;
+        }
+        self.brightness = brightness;
+
+        Ok(())
+    }
+
+    fn brightness(&self) -> u8 {
+        self.brightness
+    }
+}
+
+///
+/// Backlight dimmed by the duty cycle of a PWM channel.
+///
+pub struct PwmBacklight<P> {
+    pwm: P,
+    brightness: u8,
+}
+
+impl<P: PwmPin<Duty = u16>> PwmBacklight<P> {
+    ///
+    /// Creates the backlight from a PWM channel, enabling it at duty 0.
+    ///
+    pub fn new(mut pwm: P) -> Self {
+        pwm.set_duty(0);
+        pwm.enable();
+
+        Self { pwm, brightness: 0 }
+    }
+
+    ///
+    /// Disables and returns the PWM channel.
+    ///
+    pub fn release(mut self) -> P {
+        self.pwm.disable();
+        self.pwm
+    }
+}
+
+impl<P: PwmPin<Duty = u16>> Backlight for PwmBacklight<P> {
+    type Error = Infallible;
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Infallible> {
+        let duty = u32::from(self.pwm.get_max_duty()) * u32::from(brightness) / 255;
+        self.pwm.set_duty(duty as u16);
+        self.brightness = brightness;
+
+        Ok(())
+    }
+
+    fn brightness(&self) -> u8 {
+        self.brightness
+    }
+}
+
+///
+/// Backlight dimmed by the display itself (WRDISBV), for panels whose
+/// backlight driver follows the LEDPWM output. See `ST7796::dcs_backlight`.
+///
+pub struct DcsBacklight<'a, DI, RST, BL>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    display: &'a mut ST7796<DI, RST, BL>,
+}
+
+impl<DI, RST, BL, RstE, BlE> ST7796<DI, RST, BL>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Turns on the brightness control block (WRCTRLD) and returns a
+    /// [`Backlight`] setting the display brightness value (WRDISBV).
+    ///
+    pub fn dcs_backlight(&mut self) -> Result<DcsBacklight<'_, DI, RST, BL>, Error<RstE, BlE>> {
+        self.write_register(&BrightnessControl {
+            brightness_control: true,
+            dimming: false,
+            backlight: true,
+        })?;
+
+        Ok(DcsBacklight { display: self })
+    }
+}
+
+impl<DI, RST, BL, RstE, BlE> Backlight for DcsBacklight<'_, DI, RST, BL>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    type Error = Error<RstE, BlE>;
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Self::Error> {
+        self.display.write_register(&Brightness(brightness))
+    }
+
+    fn brightness(&self) -> u8 {
+        self.display.cache.brightness
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::mock::{MockDelay, MockPin};
+    use std::vec::Vec;
+
+    #[test]
+    fn fade_steps_cover_every_level() {
+        let (step_us, steps) = fade_steps(10, 14, 1_000);
+        assert_eq!(step_us, 250);
+        assert_eq!(steps.collect::<Vec<_>>(), [11, 12, 13, 14]);
+
+        let (_, steps) = fade_steps(3, 0, 1_000);
+        assert_eq!(steps.collect::<Vec<_>>(), [2, 1, 0]);
+
+        let (step_us, mut steps) = fade_steps(7, 7, 1_000);
+        assert_eq!(step_us, 0);
+        assert_eq!(steps.next(), None);
+    }
+
+    #[test]
+    fn fade_to_sets_every_step() {
+        let mut delay = MockDelay::default();
+        let mut bl = GpioBacklight::new(MockPin::default());
+        bl.fade_to(255, 255_000, &mut delay).unwrap();
+        bl.fade_to(0, 0, &mut delay).unwrap();
+
+        assert_eq!(bl.brightness(), 0);
+        assert_eq!(delay.total_us, 255_000);
+        let levels = bl.release().levels;
+        assert_eq!(levels.len(), 510);
+        assert!(levels[..255].iter().all(|&high| high));
+        assert!(!levels[509]);
+    }
+}
@@ -1,7 +1,8 @@
 //! Original code from: https://github.com/lupyuen/piet-embedded/blob/master/piet-embedded-graphics/src/batch.rs
 //! Batch the pixels to be rendered into Pixel Rows and Pixel Blocks (contiguous Pixel Rows).
 //! This enables the pixels to be rendered efficiently as Pixel Blocks, which may be transmitted in a single Non-Blocking SPI request.
-use crate::{Error, ST7796};
+use crate::graphics::to_rgb565;
+use crate::{Backlight, Error, ST7796};
 use display_interface::WriteOnlyDataCommand;
 use embedded_graphics_core::{
     pixelcolor::{raw::RawU16, Rgb565},
@@ -9,26 +10,31 @@ use embedded_graphics_core::{
 };
 use embedded_hal::digital::v2::OutputPin;
 
-pub trait DrawBatch<DI, RST, BL, T, PinE>
+pub trait DrawBatch<DI, RST, BL, T, RstE, BlE>
 where
     DI: WriteOnlyDataCommand,
-    RST: OutputPin<Error = PinE>,
-    BL: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
     T: IntoIterator<Item = Pixel<Rgb565>>,
 {
-    fn draw_batch(&mut self, item_pixels: T) -> Result<(), Error<PinE>>;
+    fn draw_batch(&mut self, item_pixels: T) -> Result<(), Error<RstE, BlE>>;
 }
 
-impl<DI, RST, BL, T, PinE> DrawBatch<DI, RST, BL, T, PinE> for ST7796<DI, RST, BL>
+impl<DI, RST, BL, T, RstE, BlE> DrawBatch<DI, RST, BL, T, RstE, BlE> for ST7796<DI, RST, BL>
 where
     DI: WriteOnlyDataCommand,
-    RST: OutputPin<Error = PinE>,
-    BL: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
     T: IntoIterator<Item = Pixel<Rgb565>>,
 {
-    fn draw_batch(&mut self, item_pixels: T) -> Result<(), Error<PinE>> {
-        //  Get the pixels for the item to be rendered.
-        let pixels = item_pixels.into_iter();
+    fn draw_batch(&mut self, item_pixels: T) -> Result<(), Error<RstE, BlE>> {
+        //  Get the pixels for the item to be rendered, dropping those outside the visible area.
+        let bounding_box = self.framebuffer_bounding_box();
+        let idle = self.quantize_idle();
+        let pixels = item_pixels
+            .into_iter()
+            .filter(|Pixel(point, _)| bounding_box.contains(*point))
+            .map(|Pixel(point, color)| Pixel(point, RawU16::new(to_rgb565(color, idle)).into()));
         //  Batch the pixels into Pixel Rows.
         let rows = to_rows(pixels);
         //  Batch the Pixel Rows into Pixel Blocks.
@@ -39,12 +45,13 @@ where
             x_right,
             y_top,
             y_bottom,
-            colors,
+            mut colors,
             ..
         } in blocks
         {
-            //  Render the Pixel Block.
-            self.set_pixels(x_left, y_top, x_right, y_bottom, colors)?;
+            //  Render the Pixel Block as one slice.
+            self.start_pixels(x_left, y_top, x_right, y_bottom)?;
+            self.send_pixels(&mut colors)?;
 
             //  Dump out the Pixel Blocks for the square in test_display()
             /* if x_left >= 60 && x_left <= 150 && x_right >= 60 && x_right <= 150 && y_top >= 60 && y_top <= 150 && y_bottom >= 60 && y_bottom <= 150 {
@@ -0,0 +1,105 @@
+//! Display brightness registers (WRDISBV 51h, WRCTRLD 53h, WRCABC 55h, WRCABCMB 5Eh).
+
+use crate::instruction::{Command, Instruction};
+
+///
+/// Display brightness value (WRDISBV), 0 is the lowest brightness.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Brightness(pub u8);
+
+impl Instruction for Brightness {
+    const COMMAND: Command = Command::WRDISBV;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [self.0]
+    }
+}
+
+///
+/// Brightness control block setting (WRCTRLD).
+/// The default is the reset value: everything off.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BrightnessControl {
+    /// Brightness control block on (BCTRL); when off the brightness is 00h.
+    pub brightness_control: bool,
+    /// Dimming on (DD).
+    pub dimming: bool,
+    /// Backlight control on (BL).
+    pub backlight: bool,
+}
+
+impl Instruction for BrightnessControl {
+    const COMMAND: Command = Command::WRCTRLD;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [u8::from(self.brightness_control) << 5 | u8::from(self.dimming) << 3 | u8::from(self.backlight) << 2]
+    }
+}
+
+///
+/// Content adaptive brightness control mode (`C[1:0]`).
+///
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CabcMode {
+    #[default]
+    Off = 0b00,
+    UserInterface = 0b01,
+    StillPicture = 0b10,
+    MovingImage = 0b11,
+}
+
+///
+/// Color enhancement level (`CE[1:0]`).
+///
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColorEnhancement {
+    Low = 0b00,
+    Medium = 0b01,
+    High = 0b11,
+}
+
+///
+/// Content adaptive brightness control (WRCABC).
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AdaptiveBrightness {
+    /// Adaptive brightness mode.
+    pub mode: CabcMode,
+    /// Color enhancement level, `None` to turn color enhancement off (CECTRL).
+    pub color_enhancement: Option<ColorEnhancement>,
+}
+
+impl Instruction for AdaptiveBrightness {
+    const COMMAND: Command = Command::WRCABC;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        let enhancement = match self.color_enhancement {
+            Some(level) => 0b1000_0000 | (level as u8) << 4,
+            None => 0,
+        };
+
+        [enhancement | self.mode as u8]
+    }
+}
+
+///
+/// Minimum brightness of content adaptive brightness control (WRCABCMB).
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MinimumBrightness(pub u8);
+
+impl Instruction for MinimumBrightness {
+    const COMMAND: Command = Command::WRCABCMB;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [self.0]
+    }
+}
@@ -0,0 +1,149 @@
+//! Builder collecting the pins, panel geometry and init settings before the first frame.
+
+use crate::{Backlight, Error, InitConfig, Madctl, NoPin, PanelGeometry, PixelFormat, Ready, Uninit, ST7796};
+use display_interface::WriteOnlyDataCommand;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::digital::v2::OutputPin;
+
+///
+/// Builds and initializes an [`ST7796`], see [`ST7796::builder`].
+///
+/// Everything set here is written by [`Builder::init`] before the display
+/// is switched on, so the panel never shows a frame with other settings.
+///
+pub struct Builder<DI, RST, BL> {
+    di: DI,
+    rst: Option<RST>,
+    bl: Option<BL>,
+    size: (u16, u16),
+    offset: (u16, u16),
+    config: InitConfig,
+}
+
+impl<DI> ST7796<DI, NoPin, NoPin, Uninit>
+where
+    DI: WriteOnlyDataCommand,
+{
+    ///
+    /// Starts building a driver for a 320x480 panel without reset and
+    /// backlight pins, using [`InitConfig::default`].
+    ///
+    /// # Arguments
+    ///
+    /// * `di` - Display Interface to communicate with display.
+    ///
+    pub fn builder(di: DI) -> Builder<DI, NoPin, NoPin> {
+        Builder {
+            di,
+            rst: None,
+            bl: None,
+            size: PanelGeometry::default().visible_size(),
+            offset: (0, 0),
+            config: InitConfig::default(),
+        }
+    }
+}
+
+impl<DI, RST, BL, RstE, BlE> Builder<DI, RST, BL>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Sets the hard reset pin.
+    ///
+    pub fn reset_pin<R: OutputPin>(self, rst: R) -> Builder<DI, R, BL> {
+        Builder {
+            di: self.di,
+            rst: Some(rst),
+            bl: self.bl,
+            size: self.size,
+            offset: self.offset,
+            config: self.config,
+        }
+    }
+
+    ///
+    /// Sets the backlight, such as a [`GpioBacklight`](crate::GpioBacklight).
+    ///
+    pub fn backlight_pin<B: Backlight>(self, bl: B) -> Builder<DI, RST, B> {
+        Builder {
+            di: self.di,
+            rst: self.rst,
+            bl: Some(bl),
+            size: self.size,
+            offset: self.offset,
+            config: self.config,
+        }
+    }
+
+    ///
+    /// Sets the visible size of the panel, in portrait orientation.
+    ///
+    pub fn size(mut self, size_x: u16, size_y: u16) -> Self {
+        self.size = (size_x, size_y);
+        self
+    }
+
+    ///
+    /// Sets the GRAM column and row of the top left visible pixel, in portrait orientation.
+    ///
+    pub fn offset(mut self, offset_x: u16, offset_y: u16) -> Self {
+        self.offset = (offset_x, offset_y);
+        self
+    }
+
+    ///
+    /// Sets the orientation, mirroring and color order written to MADCTL.
+    ///
+    pub fn orientation(mut self, orientation: impl Into<Madctl>) -> Self {
+        self.config = self.config.with_orientation(orientation);
+        self
+    }
+
+    ///
+    /// Selects INVON (`true`) or INVOFF (`false`).
+    ///
+    pub fn invert(mut self, inverted: bool) -> Self {
+        self.config = self.config.with_inversion(inverted);
+        self
+    }
+
+    ///
+    /// Sets the pixel format written to COLMOD.
+    ///
+    pub fn pixel_format(mut self, pixel_format: PixelFormat) -> Self {
+        self.config = self.config.with_pixel_format(pixel_format);
+        self
+    }
+
+    ///
+    /// Replaces the whole init sequence, including the orientation,
+    /// inversion and pixel format set so far.
+    ///
+    pub fn init_config(mut self, config: InitConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    ///
+    /// Creates the driver and runs the init sequence, returning the
+    /// configured driver with the display on. On failure the uninitialized
+    /// driver is handed back with the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay_source` - mutable reference to a delay provider.
+    ///
+    pub fn init(
+        self,
+        delay_source: &mut impl DelayUs<u32>,
+    ) -> Result<ST7796<DI, RST, BL, Ready>, (ST7796<DI, RST, BL, Uninit>, Error<RstE, BlE>)> {
+        let (size_x, size_y) = self.size;
+        let (offset_x, offset_y) = self.offset;
+        let geometry = PanelGeometry::new(size_x, size_y).with_offset(offset_x, offset_y);
+
+        ST7796::with_geometry(self.di, self.rst, self.bl, geometry).init_with(&self.config, delay_source)
+    }
+}
@@ -0,0 +1,217 @@
+//! Configuration of the bring-up sequence run by `ST7796::init_with`.
+
+use crate::instruction::{Command, Instruction};
+use crate::{DisplayFunction, Gamma, Madctl, PanelGeometry, PowerSettings, ScrollRegion};
+
+///
+/// Interface pixel format, as written to COLMOD (3Ah).
+///
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 16 bits per pixel, 65K colors.
+    Rgb565 = 0b0101_0101,
+    /// 18 bits per pixel, 262K colors. Sent as 3 bytes, 6 bits each (MSB aligned).
+    Rgb666 = 0b0110_0110,
+    /// 24 bits per pixel, 16.7M colors. Sent as 3 bytes.
+    Rgb888 = 0b0111_0111,
+}
+
+impl Instruction for PixelFormat {
+    const COMMAND: Command = Command::PIXFMT;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [*self as u8]
+    }
+}
+
+impl PixelFormat {
+    ///
+    /// Returns the format encoded by the MCU interface bits (D2 - D0), if defined.
+    ///
+    pub fn from_bits(bits: u8) -> Option<Self> {
+        match bits & 0b111 {
+            0b101 => Some(PixelFormat::Rgb565),
+            0b110 => Some(PixelFormat::Rgb666),
+            0b111 => Some(PixelFormat::Rgb888),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Returns the number of bytes sent for each pixel.
+    ///
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Rgb565 => 2,
+            PixelFormat::Rgb666 | PixelFormat::Rgb888 => 3,
+        }
+    }
+}
+
+///
+/// Describes the whole bring-up of the display.
+///
+/// `InitConfig::default()` is the sequence `ST7796::init` sends:
+/// hard reset (or a 120ms wait without a reset pin), SWRESET (150ms),
+/// SLPOUT (10ms), VSCRDER with all 480 lines scrolling, VSCRSADD 0,
+/// MADCTL 0 (portrait), COLMOD 0x55 (16 bit), then INVON, NORON and DISPON,
+/// each followed by 10ms, and finally the backlight at full brightness.
+///
+/// The default leaves the power, gamma and display function registers at
+/// their power-on values; when set, they are sent after COLMOD in that order.
+/// Other panel specific registers can be added with
+/// [`InitConfig::with_commands`]; they are sent after those and
+/// before INVON.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct InitConfig {
+    pub(crate) reset_delay_us: u32,
+    pub(crate) sleep_out_delay_us: u32,
+    pub(crate) scroll_region: ScrollRegion,
+    pub(crate) madctl: Madctl,
+    pub(crate) pixel_format: PixelFormat,
+    pub(crate) inverted: bool,
+    pub(crate) gamma: Option<Gamma>,
+    pub(crate) power: Option<PowerSettings>,
+    pub(crate) display_function: Option<DisplayFunction>,
+    pub(crate) backlight: Option<u8>,
+    pub(crate) commands: &'static [(Command, &'static [u8])],
+}
+
+impl Default for InitConfig {
+    fn default() -> Self {
+        Self {
+            reset_delay_us: 150_000,
+            sleep_out_delay_us: 10_000,
+            scroll_region: ScrollRegion::full(480),
+            madctl: Madctl::new(),
+            pixel_format: PixelFormat::Rgb565,
+            inverted: true,
+            gamma: None,
+            power: None,
+            display_function: None,
+            backlight: Some(u8::MAX),
+            commands: &[],
+        }
+    }
+}
+
+impl InitConfig {
+    ///
+    /// Creates the default configuration, see [`InitConfig::default`].
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Sets the time to wait after SWRESET, in microseconds.
+    /// Without a reset pin at least 120ms are waited.
+    ///
+    pub fn with_reset_delay_us(mut self, delay_us: u32) -> Self {
+        self.reset_delay_us = delay_us;
+        self
+    }
+
+    ///
+    /// Sets the time to wait after SLPOUT, in microseconds.
+    /// At least 5ms are waited before the next command.
+    ///
+    pub fn with_sleep_out_delay_us(mut self, delay_us: u32) -> Self {
+        self.sleep_out_delay_us = delay_us;
+        self
+    }
+
+    ///
+    /// Sets the vertical scrolling areas sent with VSCRDER.
+    ///
+    pub fn with_scroll_region(mut self, scroll_region: ScrollRegion) -> Self {
+        self.scroll_region = scroll_region;
+        self
+    }
+
+    ///
+    /// Sets the orientation written to MADCTL.
+    ///
+    pub fn with_orientation(mut self, orientation: impl Into<Madctl>) -> Self {
+        self.madctl = orientation.into();
+        self
+    }
+
+    ///
+    /// Sets the pixel format written to COLMOD.
+    ///
+    pub fn with_pixel_format(mut self, pixel_format: PixelFormat) -> Self {
+        self.pixel_format = pixel_format;
+        self
+    }
+
+    ///
+    /// Selects INVON (`true`) or INVOFF (`false`).
+    /// IPS panels usually need inversion, TN panels usually don't.
+    ///
+    pub fn with_inversion(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+
+    ///
+    /// Sets the power control registers, sent after the pixel format.
+    ///
+    pub fn with_power(mut self, power: PowerSettings) -> Self {
+        self.power = Some(power);
+        self
+    }
+
+    ///
+    /// Sets the gamma curves, sent after the pixel format.
+    ///
+    pub fn with_gamma(mut self, gamma: Gamma) -> Self {
+        self.gamma = Some(gamma);
+        self
+    }
+
+    ///
+    /// Sets the display function control, sent after the pixel format.
+    ///
+    pub fn with_display_function(mut self, display_function: DisplayFunction) -> Self {
+        self.display_function = Some(display_function);
+        self
+    }
+
+    ///
+    /// Sets the backlight brightness set after DISPON, `None` to leave
+    /// the backlight as it is, e.g. to fade it in afterwards.
+    ///
+    pub fn with_backlight(mut self, brightness: Option<u8>) -> Self {
+        self.backlight = brightness;
+        self
+    }
+
+    ///
+    /// Sets additional register writes, each a command and its parameters.
+    /// Command Table 2 registers are enabled with CSCON around their write.
+    /// Commands that leave the driver state (SWRESET, SLPIN, SLPOUT and
+    /// DISPOFF) make `init_with` fail with [`Error::InvalidConfig`](crate::Error::InvalidConfig)
+    /// before anything is sent.
+    ///
+    pub fn with_commands(mut self, commands: &'static [(Command, &'static [u8])]) -> Self {
+        self.commands = commands;
+        self
+    }
+
+    /// Private method:Returns true if the configuration fits a panel with
+    /// `geometry` and its commands leave the driver state alone, checked by
+    /// the drivers before the first command is sent.
+    pub(crate) fn is_valid(&self, geometry: &PanelGeometry) -> bool {
+        let (_, gram_height) = geometry.gram_size();
+        let (_, height) = geometry.visible_size();
+
+        geometry.is_valid()
+            && self.scroll_region.is_valid(gram_height)
+            && self.display_function.map_or(true, |function| function.driving_lines.lines() >= height)
+            && !self.commands.iter().any(|&(command, _)| command.changes_driver_state())
+    }
+}
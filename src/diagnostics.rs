@@ -0,0 +1,277 @@
+//! Display identification and status registers.
+
+use crate::instruction::Command;
+use crate::read::ReadWriteDataCommand;
+use crate::{Backlight, Error, Madctl, PixelFormat, ST7796};
+use embedded_hal::digital::v2::OutputPin;
+
+///
+/// Display identification, as read by RDDID (04h).
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DisplayId {
+    /// LCD module manufacturer ID (ID1).
+    pub manufacturer: u8,
+    /// LCD module/driver version ID (ID2).
+    pub version: u8,
+    /// LCD module/driver ID (ID3).
+    pub driver: u8,
+}
+
+///
+/// Display status, as read by RDDST (09h).
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DisplayStatus(u32);
+
+impl DisplayStatus {
+    /// Returns the raw 32 bit status, D31 to D0.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Booster voltage on (D31).
+    pub fn booster_on(&self) -> bool {
+        self.bit(31)
+    }
+
+    /// MADCTL address, color and refresh order bits (D30 - D26).
+    /// D25 is reserved, so the horizontal refresh order is always cleared.
+    pub fn madctl(&self) -> Madctl {
+        Madctl::from_bits((self.0 >> 23) as u8 & 0b1111_1000)
+    }
+
+    /// Interface pixel format (D22 - D20), `None` if undefined.
+    pub fn pixel_format(&self) -> Option<PixelFormat> {
+        PixelFormat::from_bits((self.0 >> 20) as u8 & 0b111)
+    }
+
+    /// Idle mode on (D19).
+    pub fn idle_mode(&self) -> bool {
+        self.bit(19)
+    }
+
+    /// Partial mode on (D18).
+    pub fn partial_mode(&self) -> bool {
+        self.bit(18)
+    }
+
+    /// Sleep out (D17), `false` while sleeping.
+    pub fn sleep_out(&self) -> bool {
+        self.bit(17)
+    }
+
+    /// Normal display mode on (D16).
+    pub fn normal_mode(&self) -> bool {
+        self.bit(16)
+    }
+
+    /// Vertical scrolling on (D15).
+    pub fn vertical_scrolling(&self) -> bool {
+        self.bit(15)
+    }
+
+    /// Display inversion on (D13).
+    pub fn inverted(&self) -> bool {
+        self.bit(13)
+    }
+
+    /// Display on (D10).
+    pub fn display_on(&self) -> bool {
+        self.bit(10)
+    }
+
+    /// Tearing effect line on (D9).
+    pub fn tearing_effect_on(&self) -> bool {
+        self.bit(9)
+    }
+
+    /// Tearing effect line mode (D5), `true` for horizontal and vertical blanking.
+    pub fn tearing_effect_horizontal(&self) -> bool {
+        self.bit(5)
+    }
+
+    /// Gamma curve selection (D8 - D6).
+    pub fn gamma_curve(&self) -> u8 {
+        (self.0 >> 6) as u8 & 0b111
+    }
+
+    fn bit(&self, bit: u32) -> bool {
+        self.0 & (1 << bit) != 0
+    }
+}
+
+///
+/// Display power mode, as read by RDDPM (0Ah).
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PowerMode(u8);
+
+impl PowerMode {
+    /// Returns the raw register value.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Booster voltage on (D7).
+    pub fn booster_on(&self) -> bool {
+        self.0 & 0b1000_0000 != 0
+    }
+
+    /// Idle mode on (D6).
+    pub fn idle_mode(&self) -> bool {
+        self.0 & 0b0100_0000 != 0
+    }
+
+    /// Partial mode on (D5).
+    pub fn partial_mode(&self) -> bool {
+        self.0 & 0b0010_0000 != 0
+    }
+
+    /// Sleep out (D4), `false` while sleeping.
+    pub fn sleep_out(&self) -> bool {
+        self.0 & 0b0001_0000 != 0
+    }
+
+    /// Normal display mode on (D3).
+    pub fn normal_mode(&self) -> bool {
+        self.0 & 0b0000_1000 != 0
+    }
+
+    /// Display on (D2).
+    pub fn display_on(&self) -> bool {
+        self.0 & 0b0000_0100 != 0
+    }
+}
+
+///
+/// Self-diagnostic result, as read by RDDSDR (0Fh).
+///
+/// The register loading and functionality bits are inverted by the
+/// controller each time its checks pass after SLPOUT, so compare a reading
+/// taken before sleep out with one taken after it.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SelfDiagnostic(u8);
+
+impl SelfDiagnostic {
+    /// Returns the raw register value.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Register loading detection (D7).
+    pub fn register_loading(&self) -> bool {
+        self.0 & 0b1000_0000 != 0
+    }
+
+    /// Functionality detection (D6).
+    pub fn functionality(&self) -> bool {
+        self.0 & 0b0100_0000 != 0
+    }
+
+    /// Checksum comparison (D0), `true` if the checksums differ.
+    pub fn checksum_mismatch(&self) -> bool {
+        self.0 & 0b0000_0001 != 0
+    }
+
+    ///
+    /// Returns true if both self checks passed between `before` (read
+    /// while sleeping) and this reading (taken after sleep out).
+    ///
+    pub fn passed_since(&self, before: SelfDiagnostic) -> bool {
+        let flipped = self.0 ^ before.0;
+        flipped & 0b1100_0000 == 0b1100_0000 && !self.checksum_mismatch()
+    }
+}
+
+impl<DI, RST, BL, S, RstE, BlE> ST7796<DI, RST, BL, S>
+where
+    DI: ReadWriteDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Reads the display identification (RDDID).
+    ///
+    pub fn read_id(&mut self) -> Result<DisplayId, Error<RstE, BlE>> {
+        let mut buf = [0u8; 4];
+        self.read_command(Command::RDDID, &mut buf)?;
+
+        Ok(DisplayId {
+            manufacturer: buf[1],
+            version: buf[2],
+            driver: buf[3],
+        })
+    }
+
+    ///
+    /// Reads the display status (RDDST).
+    ///
+    pub fn read_status(&mut self) -> Result<DisplayStatus, Error<RstE, BlE>> {
+        let mut buf = [0u8; 5];
+        self.read_command(Command::RDDST, &mut buf)?;
+
+        Ok(DisplayStatus(u32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]])))
+    }
+
+    ///
+    /// Reads the display power mode (RDDPM).
+    ///
+    pub fn read_power_mode(&mut self) -> Result<PowerMode, Error<RstE, BlE>> {
+        Ok(PowerMode(self.read_register(Command::RDMODE)?))
+    }
+
+    ///
+    /// Reads the MADCTL value the display is using (RDDMADCTL).
+    ///
+    pub fn read_madctl(&mut self) -> Result<Madctl, Error<RstE, BlE>> {
+        Ok(Madctl::from_bits(self.read_register(Command::RDMADCTL)?))
+    }
+
+    ///
+    /// Reads the interface pixel format the display is using (RDDCOLMOD).
+    /// Returns `None` if the format is undefined.
+    ///
+    pub fn read_pixel_format(&mut self) -> Result<Option<PixelFormat>, Error<RstE, BlE>> {
+        Ok(PixelFormat::from_bits(self.read_register(Command::RDPIXFMT)? & 0b111))
+    }
+
+    ///
+    /// Reads the self-diagnostic result (RDDSDR).
+    ///
+    pub fn self_diagnostic(&mut self) -> Result<SelfDiagnostic, Error<RstE, BlE>> {
+        Ok(SelfDiagnostic(self.read_register(Command::RDSELFDIAG)?))
+    }
+
+    /// Private method:Reads a single parameter register.
+    fn read_register(&mut self, command: Command) -> Result<u8, Error<RstE, BlE>> {
+        let mut buf = [0u8; 2];
+        self.read_command(command, &mut buf)?;
+
+        Ok(buf[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_madctl_skips_booster_and_reserved_bits() {
+        let booster = DisplayStatus(1 << 31);
+        assert!(booster.booster_on());
+        assert_eq!(booster.madctl(), Madctl::new());
+
+        let status = DisplayStatus(0b0101_0110 << 24 | 1 << 23);
+        let madctl = status.madctl();
+        assert!(!status.booster_on());
+        assert!(madctl.mirror_y());
+        assert!(!madctl.mirror_x());
+        assert!(madctl.swap_xy());
+        assert!(!madctl.vertical_refresh_bottom_to_top());
+        assert!(madctl.bgr());
+        // D25 is reserved and must not read as MH
+        assert!(!madctl.horizontal_refresh_right_to_left());
+    }
+}
@@ -0,0 +1,291 @@
+//! Display Function Control (DFC, B6h): scan directions, non-display area and driving lines,
+//! with the other display output registers: Interface Mode (IFMODE, B0h), Entry Mode (EM, B7h)
+//! and Display Output Ctrl Adjust (DOCA, E8h).
+
+use crate::instruction::{Command, Instruction};
+
+///
+/// Gate output scan mode in the non-display area of partial mode (PTG).
+///
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum NonDisplayScan {
+    #[default]
+    Normal = 0b00,
+    /// Scan once every [`IntervalScan`] frames.
+    Interval = 0b10,
+}
+
+///
+/// Source and VCOM output in the non-display area of partial mode (PT).
+///
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum NonDisplaySource {
+    #[default]
+    V63 = 0b00,
+    V0 = 0b01,
+}
+
+///
+/// Interval scan cycle (ISC), an odd number of frames from 1 to 31.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IntervalScan(u8);
+
+impl IntervalScan {
+    ///
+    /// Creates the setting from the scan cycle, `1..=31` frames, odd.
+    ///
+    pub const fn frames(frames: u8) -> Option<Self> {
+        if frames > 31 || frames % 2 == 0 {
+            return None;
+        }
+
+        Some(Self(frames / 2))
+    }
+
+    /// Returns the ISC code.
+    pub const fn code(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the scan cycle in frames.
+    pub const fn frame_count(self) -> u8 {
+        self.0 * 2 + 1
+    }
+}
+
+impl Default for IntervalScan {
+    /// Register default: 2h (5 frames).
+    fn default() -> Self {
+        Self(0x02)
+    }
+}
+
+///
+/// Number of gate lines driven (NL), in steps of 8 lines.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DrivingLines(u8);
+
+impl DrivingLines {
+    ///
+    /// Creates the setting from the line count, `8..=480` in steps of 8.
+    ///
+    pub const fn new(lines: u16) -> Option<Self> {
+        if lines < 8 || lines > 480 || lines % 8 != 0 {
+            return None;
+        }
+
+        Some(Self((lines / 8 - 1) as u8))
+    }
+
+    /// Returns the NL code.
+    pub const fn code(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the number of driven lines.
+    pub const fn lines(self) -> u16 {
+        (self.0 as u16 + 1) * 8
+    }
+}
+
+impl Default for DrivingLines {
+    /// Register default: 3Bh (480 lines).
+    fn default() -> Self {
+        Self(0x3B)
+    }
+}
+
+///
+/// Display Function Control setting, sent by `ST7796::set_display_function`.
+/// The default is the register default of the datasheet.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DisplayFunction {
+    /// Gate scan mode in the non-display area (PTG).
+    pub non_display_scan: NonDisplayScan,
+    /// Source output in the non-display area (PT).
+    pub non_display_source: NonDisplaySource,
+    /// Gate scan from G480 to G1 (GS).
+    pub gate_scan_reversed: bool,
+    /// Source output from S960 to S1 (SS).
+    pub source_scan_reversed: bool,
+    /// Scan odd gate lines first, then even ones (SM).
+    pub interlaced_gates: bool,
+    /// Scan cycle used by [`NonDisplayScan::Interval`] (ISC).
+    pub interval_scan: IntervalScan,
+    /// Driven gate lines (NL), not less than the panel height.
+    pub driving_lines: DrivingLines,
+}
+
+impl Instruction for DisplayFunction {
+    const COMMAND: Command = Command::DFUNCTR;
+    type Bytes = [u8; 3];
+
+    /// The RGB interface bits keep their defaults.
+    fn to_bytes(&self) -> [u8; 3] {
+        [
+            0b1000_0000 | (self.non_display_scan as u8) << 2 | self.non_display_source as u8,
+            u8::from(self.gate_scan_reversed) << 6
+                | u8::from(self.source_scan_reversed) << 5
+                | u8::from(self.interlaced_gates) << 4
+                | self.interval_scan.code(),
+            self.driving_lines.code(),
+        ]
+    }
+}
+
+///
+/// Interface Mode Control (IFMODE). The default is the reset value.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct InterfaceMode {
+    /// Use DIN/SDA for both directions of the serial interface, DOUT unused (SPI_EN).
+    pub spi_single_data_line: bool,
+    /// VSYNC active high (VSCP).
+    pub vsync_high: bool,
+    /// HSYNC active high (HSCP).
+    pub hsync_high: bool,
+    /// Fetch data at the falling PCLK edge (PKP).
+    pub pclk_falling: bool,
+    /// DE active low (DEP).
+    pub de_low: bool,
+}
+
+impl Instruction for InterfaceMode {
+    const COMMAND: Command = Command::RGB_INTERFACE;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [u8::from(self.spi_single_data_line) << 7
+            | u8::from(self.vsync_high) << 3
+            | u8::from(self.hsync_high) << 2
+            | u8::from(self.pclk_falling) << 1
+            | u8::from(self.de_low)]
+    }
+}
+
+///
+/// How 16 bit colors are expanded to the 18 bit frame memory (EPF).
+///
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ColorExpansion {
+    /// Red and blue LSB set to 0.
+    #[default]
+    Zero = 0b00,
+    /// Red and blue LSB set to 1.
+    One = 0b01,
+    /// Red and blue LSB copied from their MSB.
+    Msb = 0b10,
+    /// Red and blue LSB copied from the green LSB.
+    GreenLsb = 0b11,
+}
+
+///
+/// Gate driver output (GON, DTE).
+///
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GateOutput {
+    /// All gate outputs at VGH.
+    Vgh = 0b00,
+    /// All gate outputs at VGL.
+    Vgl = 0b10,
+    /// Normal display.
+    #[default]
+    Normal = 0b11,
+}
+
+///
+/// Entry Mode Set (EM). The default is the register default, 06h.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct EntryMode {
+    /// 16 to 18 bit color expansion.
+    pub color_expansion: ColorExpansion,
+    /// Gate driver output.
+    pub gate_output: GateOutput,
+    /// Deep standby mode (DSTB). Frame memory and registers are lost;
+    /// only a hardware reset or 6 CSX pulses leave it.
+    pub deep_standby: bool,
+}
+
+impl Instruction for EntryMode {
+    const COMMAND: Command = Command::EM;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [(self.color_expansion as u8) << 6 | u8::from(self.deep_standby) << 3 | (self.gate_output as u8) << 1]
+    }
+}
+
+///
+/// Source and gate output timing (DOCA).
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OutputTiming {
+    source_equalizing: u8,
+    gate_start: u8,
+    gate_equalizing: bool,
+    gate_end: u8,
+}
+
+impl OutputTiming {
+    ///
+    /// Creates the setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_equalizing` - S_END, `0..=15`: 9us + 1.5us per step.
+    /// * `gate_start` - G_START, `0..=63` clocks.
+    /// * `gate_equalizing` - G_EQ.
+    /// * `gate_end` - G_END, `0..=63` clocks.
+    ///
+    pub const fn new(source_equalizing: u8, gate_start: u8, gate_equalizing: bool, gate_end: u8) -> Option<Self> {
+        if source_equalizing > 0x0F || gate_start > 0x3F || gate_end > 0x3F {
+            return None;
+        }
+
+        Some(Self {
+            source_equalizing,
+            gate_start,
+            gate_equalizing,
+            gate_end,
+        })
+    }
+}
+
+impl Default for OutputTiming {
+    /// Register defaults: 25h, 0Ah, 38h.
+    fn default() -> Self {
+        Self {
+            source_equalizing: 0x05,
+            gate_start: 0x0A,
+            gate_equalizing: false,
+            gate_end: 0x38,
+        }
+    }
+}
+
+impl Instruction for OutputTiming {
+    const COMMAND: Command = Command::DOCA;
+    type Bytes = [u8; 8];
+
+    /// The fixed parameters are sent with their datasheet values.
+    fn to_bytes(&self) -> [u8; 8] {
+        [
+            0x40,
+            0x8A,
+            0x00,
+            0x00,
+            0x20 | self.source_equalizing,
+            self.gate_start,
+            u8::from(self.gate_equalizing) << 7 | self.gate_end,
+            0x33,
+        ]
+    }
+}
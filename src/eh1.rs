@@ -0,0 +1,86 @@
+//! embedded-hal 1.0 pins and delays, for HALs without embedded-hal 0.2 support.
+//!
+//! The driver is written against embedded-hal 0.2; [`Eh1Pin`] and
+//! [`Eh1Delay`] wrap the 1.0 traits so `init`, `hard_reset`,
+//! `set_backlight` and the other pin and delay users accept them.
+//! Those methods keep their 0.2 bounds, since taking both trait versions
+//! directly would need overlapping blanket implementations, so 1.0 pins
+//! and delays have to be wrapped:
+//!
+//! ```ignore
+//! let bl = GpioBacklight::new(Eh1Pin(bl));
+//! let display = ST7796::new(di, Some(Eh1Pin(rst)), Some(bl), 320, 480)
+//!     .init(&mut Eh1Delay(&mut delay))
+//!     .map_err(|(_, error)| error)?;
+//! ```
+
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::digital::v2::OutputPin;
+use embedded_hal_1::delay::DelayNs;
+
+///
+/// embedded-hal 1.0 output pin, used as a reset pin or in a [`GpioBacklight`](crate::GpioBacklight).
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Eh1Pin<P>(pub P);
+
+impl<P: embedded_hal_1::digital::OutputPin> OutputPin for Eh1Pin<P> {
+    type Error = P::Error;
+
+    fn set_low(&mut self) -> Result<(), P::Error> {
+        self.0.set_low()
+    }
+
+    fn set_high(&mut self) -> Result<(), P::Error> {
+        self.0.set_high()
+    }
+}
+
+///
+/// embedded-hal 1.0 delay provider, passed where the driver takes a delay.
+/// Wrap a mutable reference to keep using the delay afterwards.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Eh1Delay<D>(pub D);
+
+impl<D: DelayNs> DelayUs<u32> for Eh1Delay<D> {
+    fn delay_us(&mut self, us: u32) {
+        self.0.delay_us(us);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockDelay, MockInterface, MockPin};
+    use crate::{BacklightState, GpioBacklight, ST7796};
+
+    #[test]
+    fn init_resets_with_eh1_pins() {
+        let mut delay = MockDelay::default();
+        let bl = GpioBacklight::new(Eh1Pin(MockPin::default()));
+        let display = ST7796::new(MockInterface::default(), Some(Eh1Pin(MockPin::default())), Some(bl), 320, 480)
+            .init(&mut Eh1Delay(&mut delay))
+            .unwrap_or_else(|_| panic!("init failed"));
+
+        assert_eq!(delay.total_us, 30 + 150_000 + 10_000 + 30_000);
+        let (_, rst, bl) = display.release();
+        assert_eq!(rst.unwrap().0.levels, [true, false, true]);
+        assert_eq!(bl.unwrap().release().0.levels, [true]);
+    }
+
+    #[test]
+    fn hard_reset_and_backlight_use_eh1_pins() {
+        let mut delay = MockDelay::default();
+        let bl = GpioBacklight::new(Eh1Pin(MockPin::default()));
+        let mut display = ST7796::new(MockInterface::default(), Some(Eh1Pin(MockPin::default())), Some(bl), 320, 480);
+        display.hard_reset(&mut Eh1Delay(&mut delay)).unwrap();
+        display.set_backlight(BacklightState::ON, &mut Eh1Delay(&mut delay)).unwrap();
+        display.set_backlight(BacklightState::OFF, &mut Eh1Delay(&mut delay)).unwrap();
+
+        assert_eq!(delay.total_us, 30 + 10 + 10);
+        let (_, rst, bl) = display.release();
+        assert_eq!(rst.unwrap().0.levels, [true, false, true]);
+        assert_eq!(bl.unwrap().release().0.levels, [true, false]);
+    }
+}
@@ -0,0 +1,375 @@
+//! Frame rate (FRMCTR1 B1h, FRMCTR2 B2h, FRMCTR3 B3h) and display inversion (DIC B4h) control.
+//!
+//! The datasheet frame rate is
+//! `10^7 / ((168 + RTN + 32 * (15 - FRS)) * 2^DIV * (480 + VFP + VBP))` Hz.
+//! Rates here use the default blanking porches (VFP = VBP = 2 lines).
+
+use crate::instruction::{Command, Instruction};
+
+/// Lines per frame: 480 display lines and the default front and back porch.
+const LINES_PER_FRAME: u64 = 480 + 2 + 2;
+
+/// Internal oscillator frequency used by the frame rate formula, in millihertz.
+const FOSC_MILLIHERTZ: u64 = 10_000_000_000;
+
+///
+/// Division ratio of the internal clock (DIVA).
+///
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClockDivider {
+    Fosc = 0b00,
+    Fosc2 = 0b01,
+    Fosc4 = 0b10,
+    Fosc8 = 0b11,
+}
+
+impl ClockDivider {
+    const ALL: [ClockDivider; 4] = [Self::Fosc, Self::Fosc2, Self::Fosc4, Self::Fosc8];
+
+    /// Returns the divider encoded by the low 2 bits of `bits`.
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        Self::ALL[usize::from(bits & 0b11)]
+    }
+}
+
+///
+/// Frame rate setting: clock divider, FRS and line period (RTN).
+///
+/// Idle and partial mode registers only hold the line period, see
+/// [`FrameRate::line_period`].
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FrameRate {
+    divider: ClockDivider,
+    frs: u8,
+    rtn: u8,
+}
+
+impl FrameRate {
+    ///
+    /// Creates a normal mode (FRMCTR1) frame rate setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `divider` - internal clock division ratio.
+    /// * `frs` - `FRS[3:0]`, `0..=15`.
+    /// * `rtn` - `RTNA[4:0]`, `0..=31`.
+    ///
+    pub const fn new(divider: ClockDivider, frs: u8, rtn: u8) -> Option<Self> {
+        if frs > 0x0F || rtn > 0x1F {
+            return None;
+        }
+
+        Some(Self { divider, frs, rtn })
+    }
+
+    ///
+    /// Creates an idle or partial mode (FRMCTR2/3) frame rate setting from
+    /// RTNB/RTNC, `0..=31`. The clock is not divided and FRS is 0.
+    ///
+    pub const fn line_period(rtn: u8) -> Option<Self> {
+        Self::new(ClockDivider::Fosc, 0, rtn)
+    }
+
+    ///
+    /// Returns the normal mode setting closest to `millihertz`.
+    ///
+    pub fn closest(millihertz: u32) -> Self {
+        let mut best = Self::default();
+        for divider in ClockDivider::ALL {
+            for frs in 0..=0x0F {
+                for rtn in 0..=0x1F {
+                    let candidate = Self { divider, frs, rtn };
+                    if candidate.distance(millihertz) < best.distance(millihertz) {
+                        best = candidate;
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    ///
+    /// Returns the idle or partial mode setting closest to `millihertz`.
+    ///
+    pub fn closest_line_period(millihertz: u32) -> Self {
+        (0..=0x1F)
+            .map(|rtn| Self { divider: ClockDivider::Fosc, frs: 0, rtn })
+            .min_by_key(|candidate| candidate.distance(millihertz))
+            .unwrap_or_default()
+    }
+
+    /// Returns the clock divider.
+    pub const fn divider(&self) -> ClockDivider {
+        self.divider
+    }
+
+    /// Returns FRS.
+    pub const fn frs(&self) -> u8 {
+        self.frs
+    }
+
+    /// Returns the line period code (RTN).
+    pub const fn rtn(&self) -> u8 {
+        self.rtn
+    }
+
+    ///
+    /// Returns true if the setting fits the idle and partial mode registers.
+    ///
+    pub fn is_line_period(&self) -> bool {
+        self.divider == ClockDivider::Fosc && self.frs == 0
+    }
+
+    ///
+    /// Returns the resulting frame rate in millihertz.
+    ///
+    pub fn millihertz(&self) -> u32 {
+        let clocks = u64::from(self.clocks_per_line()) << self.divider as u8;
+        (FOSC_MILLIHERTZ / (clocks * LINES_PER_FRAME)) as u32
+    }
+
+    /// Returns the oscillator clocks per line, before the divider.
+    pub fn clocks_per_line(&self) -> u16 {
+        168 + u16::from(self.rtn) + 32 * u16::from(0x0F - self.frs)
+    }
+
+    fn distance(&self, millihertz: u32) -> u32 {
+        self.millihertz().abs_diff(millihertz)
+    }
+}
+
+impl Default for FrameRate {
+    /// Register default: A0h, 10h (about 60Hz).
+    fn default() -> Self {
+        Self {
+            divider: ClockDivider::Fosc,
+            frs: 0x0A,
+            rtn: 0x10,
+        }
+    }
+}
+
+///
+/// Frame rate of normal mode (FRMCTR1).
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NormalFrameRate(pub FrameRate);
+
+impl Instruction for NormalFrameRate {
+    const COMMAND: Command = Command::FRMCTR1;
+    type Bytes = [u8; 2];
+
+    fn to_bytes(&self) -> [u8; 2] {
+        [self.0.frs << 4 | self.0.divider as u8, self.0.rtn]
+    }
+}
+
+///
+/// Frame rate of idle mode (FRMCTR2); only the line period is sent.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct IdleFrameRate(pub FrameRate);
+
+impl Instruction for IdleFrameRate {
+    const COMMAND: Command = Command::FRMCTR2;
+    type Bytes = [u8; 2];
+
+    fn to_bytes(&self) -> [u8; 2] {
+        [0, self.0.rtn]
+    }
+}
+
+///
+/// Frame rate of partial mode (FRMCTR3); only the line period is sent.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartialFrameRate(pub FrameRate);
+
+impl Instruction for PartialFrameRate {
+    const COMMAND: Command = Command::FRMCTR3;
+    type Bytes = [u8; 2];
+
+    fn to_bytes(&self) -> [u8; 2] {
+        [0, self.0.rtn]
+    }
+}
+
+///
+/// Blanking porches (BPC) in lines, `2..=255` each.
+///
+/// The frame rates computed by [`FrameRate::millihertz`] assume the
+/// default of 2 lines for both vertical porches.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BlankingPorch {
+    vfp: u8,
+    vbp: u8,
+    hbp: u8,
+}
+
+impl BlankingPorch {
+    ///
+    /// Creates the setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `vfp` - vertical front porch lines.
+    /// * `vbp` - vertical back porch lines.
+    /// * `hbp` - horizontal back porch clocks.
+    ///
+    pub const fn new(vfp: u8, vbp: u8, hbp: u8) -> Option<Self> {
+        if vfp < 2 || vbp < 2 || hbp < 2 {
+            return None;
+        }
+
+        Some(Self { vfp, vbp, hbp })
+    }
+}
+
+impl Default for BlankingPorch {
+    /// Register defaults: 02h, 02h, 00h, 04h.
+    fn default() -> Self {
+        Self { vfp: 2, vbp: 2, hbp: 4 }
+    }
+}
+
+impl Instruction for BlankingPorch {
+    const COMMAND: Command = Command::BPC;
+    type Bytes = [u8; 4];
+
+    fn to_bytes(&self) -> [u8; 4] {
+        [self.vfp, self.vbp, 0, self.hbp]
+    }
+}
+
+///
+/// Frame rates of the normal, idle and partial display modes.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FrameRates {
+    /// Normal mode, full colors (FRMCTR1).
+    pub normal: FrameRate,
+    /// Idle mode, 8 colors (FRMCTR2); a line period setting.
+    pub idle: FrameRate,
+    /// Partial mode, full colors (FRMCTR3); a line period setting.
+    pub partial: FrameRate,
+}
+
+impl FrameRates {
+    ///
+    /// Returns the settings closest to the given rates, in millihertz.
+    ///
+    pub fn closest(normal: u32, idle: u32, partial: u32) -> Self {
+        Self {
+            normal: FrameRate::closest(normal),
+            idle: FrameRate::closest_line_period(idle),
+            partial: FrameRate::closest_line_period(partial),
+        }
+    }
+
+    ///
+    /// Returns true if the idle and partial settings are line period settings.
+    ///
+    pub fn is_valid(&self) -> bool {
+        self.idle.is_line_period() && self.partial.is_line_period()
+    }
+}
+
+impl Default for FrameRates {
+    /// Register defaults: A0h 10h, 00h 10h, 00h 10h.
+    fn default() -> Self {
+        let line_period = FrameRate {
+            divider: ClockDivider::Fosc,
+            frs: 0,
+            rtn: 0x10,
+        };
+
+        Self {
+            normal: FrameRate::default(),
+            idle: line_period,
+            partial: line_period,
+        }
+    }
+}
+
+///
+/// Display inversion mode (DINV).
+///
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum InversionMode {
+    Column = 0b00,
+    #[default]
+    OneDot = 0b01,
+    TwoDot = 0b10,
+}
+
+impl Instruction for InversionMode {
+    const COMMAND: Command = Command::INVCTR;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [*self as u8]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_normal_rate_is_about_60_hz() {
+        let rate = FrameRate::default();
+        assert_eq!(rate.clocks_per_line(), 344);
+        // 10^7 / (344 * 484) Hz
+        assert_eq!(rate.millihertz(), 60_061);
+        assert_eq!(NormalFrameRate(rate).to_bytes(), [0xA0, 0x10]);
+    }
+
+    #[test]
+    fn millihertz_follows_datasheet_formula() {
+        let rate = FrameRate::new(ClockDivider::Fosc4, 0x03, 0x05).unwrap();
+        let clocks = 168 + 5 + 32 * (15 - 3);
+        assert_eq!(u64::from(rate.millihertz()), 10_000_000_000 / (clocks * 4 * 484));
+    }
+
+    #[test]
+    fn new_rejects_out_of_range_fields() {
+        assert!(FrameRate::new(ClockDivider::Fosc, 0x10, 0).is_none());
+        assert!(FrameRate::new(ClockDivider::Fosc, 0, 0x20).is_none());
+        assert!(FrameRate::line_period(0x1F).is_some());
+    }
+
+    #[test]
+    fn closest_finds_exact_settings() {
+        let rate = FrameRate::new(ClockDivider::Fosc2, 0x07, 0x0C).unwrap();
+        assert_eq!(FrameRate::closest(rate.millihertz()).millihertz(), rate.millihertz());
+        // at least as close as the register default
+        assert!(FrameRate::closest(60_000).millihertz().abs_diff(60_000) <= 61);
+    }
+
+    #[test]
+    fn closest_line_period_keeps_registers_valid() {
+        let rates = FrameRates::closest(60_000, 30_000, 90_000);
+        assert!(rates.is_valid());
+
+        let slowest = FrameRate::line_period(0x1F).unwrap();
+        assert_eq!(FrameRate::closest_line_period(1_000), slowest);
+        let fastest = FrameRate::line_period(0).unwrap();
+        assert_eq!(FrameRate::closest_line_period(u32::MAX), fastest);
+        assert_eq!(IdleFrameRate(slowest).to_bytes(), [0, 0x1F]);
+    }
+
+    #[test]
+    fn rates_with_frs_are_not_line_periods() {
+        let rates = FrameRates {
+            idle: FrameRate::default(),
+            ..FrameRates::default()
+        };
+        assert!(FrameRates::default().is_valid());
+        assert!(!rates.is_valid());
+    }
+}
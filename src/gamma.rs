@@ -0,0 +1,210 @@
+//! Gamma correction (PGC E0h / NGC E1h).
+
+use crate::instruction::{Command, Instruction};
+
+///
+/// One gamma curve, as sent with PGC (positive) or NGC (negative).
+///
+/// Fields are the voltage adjustments of the datasheet table, named after
+/// the gray level they set. Values wider than their field are truncated.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GammaCurve {
+    /// V63, 4 bits.
+    pub v63: u8,
+    /// V0, 4 bits.
+    pub v0: u8,
+    /// V1, 6 bits.
+    pub v1: u8,
+    /// V2, 6 bits.
+    pub v2: u8,
+    /// V4, 5 bits.
+    pub v4: u8,
+    /// V6, 5 bits.
+    pub v6: u8,
+    /// J0, 2 bits.
+    pub j0: u8,
+    /// V13, 4 bits.
+    pub v13: u8,
+    /// V20, 7 bits.
+    pub v20: u8,
+    /// V36, 3 bits.
+    pub v36: u8,
+    /// V27, 3 bits.
+    pub v27: u8,
+    /// V43, 7 bits.
+    pub v43: u8,
+    /// J1, 2 bits.
+    pub j1: u8,
+    /// V50, 4 bits.
+    pub v50: u8,
+    /// V57, 5 bits.
+    pub v57: u8,
+    /// V59, 5 bits.
+    pub v59: u8,
+    /// V61, 6 bits.
+    pub v61: u8,
+    /// V62, 6 bits.
+    pub v62: u8,
+}
+
+impl GammaCurve {
+    ///
+    /// Decodes the 14 register parameters.
+    ///
+    pub const fn from_bytes(bytes: [u8; 14]) -> Self {
+        Self {
+            v63: bytes[0] >> 4,
+            v0: bytes[0] & 0x0F,
+            v1: bytes[1] & 0x3F,
+            v2: bytes[2] & 0x3F,
+            v4: bytes[3] & 0x1F,
+            v6: bytes[4] & 0x1F,
+            j0: (bytes[5] >> 4) & 0x03,
+            v13: bytes[5] & 0x0F,
+            v20: bytes[6] & 0x7F,
+            v36: (bytes[7] >> 4) & 0x07,
+            v27: bytes[7] & 0x07,
+            v43: bytes[8] & 0x7F,
+            j1: (bytes[9] >> 4) & 0x03,
+            v50: bytes[9] & 0x0F,
+            v57: bytes[10] & 0x1F,
+            v59: bytes[11] & 0x1F,
+            v61: bytes[12] & 0x3F,
+            v62: bytes[13] & 0x3F,
+        }
+    }
+
+    ///
+    /// Encodes the 14 register parameters.
+    ///
+    pub const fn to_bytes(&self) -> [u8; 14] {
+        [
+            (self.v63 & 0x0F) << 4 | (self.v0 & 0x0F),
+            self.v1 & 0x3F,
+            self.v2 & 0x3F,
+            self.v4 & 0x1F,
+            self.v6 & 0x1F,
+            (self.j0 & 0x03) << 4 | (self.v13 & 0x0F),
+            self.v20 & 0x7F,
+            (self.v36 & 0x07) << 4 | (self.v27 & 0x07),
+            self.v43 & 0x7F,
+            (self.j1 & 0x03) << 4 | (self.v50 & 0x0F),
+            self.v57 & 0x1F,
+            self.v59 & 0x1F,
+            self.v61 & 0x3F,
+            self.v62 & 0x3F,
+        ]
+    }
+}
+
+///
+/// Positive and negative gamma curves.
+///
+/// The controller's power-on curve is kept unless `InitConfig::with_gamma` is used.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Gamma {
+    /// Curve sent with PGC (E0h).
+    pub positive: GammaCurve,
+    /// Curve sent with NGC (E1h).
+    pub negative: GammaCurve,
+}
+
+impl Gamma {
+    ///
+    /// Values from the Sitronix reference initialisation code.
+    ///
+    pub const SITRONIX_REFERENCE: Gamma = Gamma {
+        positive: GammaCurve::from_bytes([
+            0xF0, 0x09, 0x0B, 0x06, 0x04, 0x15, 0x2F, 0x54, 0x42, 0x3C, 0x17, 0x14, 0x18, 0x1B,
+        ]),
+        negative: GammaCurve::from_bytes([
+            0xE0, 0x09, 0x0B, 0x06, 0x04, 0x03, 0x2B, 0x43, 0x42, 0x3B, 0x16, 0x14, 0x17, 0x1B,
+        ]),
+    };
+
+    ///
+    /// Steeper curve from the example code of the common 3.5 inch ST7796S IPS
+    /// modules, with deeper blacks than [`Gamma::SITRONIX_REFERENCE`].
+    ///
+    pub const IPS_HIGH_CONTRAST: Gamma = Gamma {
+        positive: GammaCurve::from_bytes([
+            0xF0, 0x09, 0x13, 0x12, 0x12, 0x2B, 0x3C, 0x44, 0x4B, 0x1B, 0x18, 0x17, 0x1D, 0x21,
+        ]),
+        negative: GammaCurve::from_bytes([
+            0xF0, 0x09, 0x13, 0x0C, 0x0D, 0x27, 0x3B, 0x44, 0x4D, 0x0B, 0x17, 0x17, 0x1D, 0x21,
+        ]),
+    };
+
+    ///
+    /// Creates a gamma setting using `curve` for both polarities.
+    ///
+    pub const fn symmetric(curve: GammaCurve) -> Self {
+        Self {
+            positive: curve,
+            negative: curve,
+        }
+    }
+}
+
+///
+/// Positive gamma curve (PGC).
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PositiveGamma(pub GammaCurve);
+
+impl Instruction for PositiveGamma {
+    const COMMAND: Command = Command::GMCTRP1;
+    type Bytes = [u8; 14];
+
+    fn to_bytes(&self) -> [u8; 14] {
+        self.0.to_bytes()
+    }
+}
+
+///
+/// Negative gamma curve (NGC).
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NegativeGamma(pub GammaCurve);
+
+impl Instruction for NegativeGamma {
+    const COMMAND: Command = Command::GMCTRN1;
+    type Bytes = [u8; 14];
+
+    fn to_bytes(&self) -> [u8; 14] {
+        self.0.to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_round_trip() {
+        for gamma in [Gamma::SITRONIX_REFERENCE, Gamma::IPS_HIGH_CONTRAST] {
+            for curve in [gamma.positive, gamma.negative] {
+                assert_eq!(GammaCurve::from_bytes(curve.to_bytes()), curve);
+            }
+        }
+        assert_eq!(
+            PositiveGamma(Gamma::SITRONIX_REFERENCE.positive).to_bytes(),
+            [0xF0, 0x09, 0x0B, 0x06, 0x04, 0x15, 0x2F, 0x54, 0x42, 0x3C, 0x17, 0x14, 0x18, 0x1B],
+        );
+    }
+
+    #[test]
+    fn fields_are_truncated_to_their_width() {
+        let curve = GammaCurve::from_bytes([0xFF; 14]);
+        assert_eq!(curve.v63, 0x0F);
+        assert_eq!(curve.j0, 0x03);
+        assert_eq!(curve.v20, 0x7F);
+        assert_eq!(curve.v27, 0x07);
+        assert_eq!(
+            curve.to_bytes(),
+            [0xFF, 0x3F, 0x3F, 0x1F, 0x1F, 0x3F, 0x7F, 0x77, 0x7F, 0x3F, 0x1F, 0x1F, 0x3F, 0x3F],
+        );
+    }
+}
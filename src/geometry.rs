@@ -0,0 +1,167 @@
+//! Panel geometry: the visible area of the glass and where it sits in GRAM.
+
+use crate::Madctl;
+
+///
+/// Visible size, GRAM size and GRAM offset of a panel.
+///
+/// All values are given in the native (portrait, MADCTL 0) orientation;
+/// the size and offset for other orientations are derived from the
+/// MADCTL mirror and exchange bits.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PanelGeometry {
+    width: u16,
+    height: u16,
+    gram_width: u16,
+    gram_height: u16,
+    offset_x: u16,
+    offset_y: u16,
+}
+
+impl Default for PanelGeometry {
+    fn default() -> Self {
+        Self::new(320, 480)
+    }
+}
+
+impl PanelGeometry {
+    ///
+    /// Creates the geometry of a panel with the given visible size,
+    /// placed at the origin of a 320x480 GRAM.
+    ///
+    /// The geometry is checked by `init`, see [`PanelGeometry::is_valid`].
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - visible columns in portrait orientation.
+    /// * `height` - visible rows in portrait orientation.
+    ///
+    pub const fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            gram_width: 320,
+            gram_height: 480,
+            offset_x: 0,
+            offset_y: 0,
+        }
+    }
+
+    ///
+    /// Sets the size of the controller GRAM.
+    ///
+    pub const fn with_gram_size(mut self, gram_width: u16, gram_height: u16) -> Self {
+        self.gram_width = gram_width;
+        self.gram_height = gram_height;
+        self
+    }
+
+    ///
+    /// Sets the GRAM column and row of the top left visible pixel in
+    /// portrait orientation.
+    ///
+    pub const fn with_offset(mut self, offset_x: u16, offset_y: u16) -> Self {
+        self.offset_x = offset_x;
+        self.offset_y = offset_y;
+        self
+    }
+
+    ///
+    /// Returns the visible size in portrait orientation.
+    ///
+    pub fn visible_size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    ///
+    /// Returns the GRAM size in portrait orientation.
+    ///
+    pub fn gram_size(&self) -> (u16, u16) {
+        (self.gram_width, self.gram_height)
+    }
+
+    ///
+    /// Returns true if the visible area is not empty and lies inside the GRAM.
+    ///
+    pub fn is_valid(&self) -> bool {
+        let right = u32::from(self.offset_x) + u32::from(self.width);
+        let bottom = u32::from(self.offset_y) + u32::from(self.height);
+        self.width > 0
+            && self.height > 0
+            && right <= u32::from(self.gram_width)
+            && bottom <= u32::from(self.gram_height)
+    }
+
+    ///
+    /// Returns the visible (width, height) for the given MADCTL setting.
+    ///
+    pub fn size(&self, madctl: Madctl) -> (u16, u16) {
+        if madctl.swap_xy() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    ///
+    /// Returns the (CASET, RASET) offset of the visible area for the given MADCTL setting.
+    ///
+    pub fn offset(&self, madctl: Madctl) -> (u16, u16) {
+        let x = if madctl.mirror_x() {
+            self.gram_width.saturating_sub(self.width + self.offset_x)
+        } else {
+            self.offset_x
+        };
+        let y = if madctl.mirror_y() {
+            self.gram_height.saturating_sub(self.height + self.offset_y)
+        } else {
+            self.offset_y
+        };
+
+        if madctl.swap_xy() {
+            (y, x)
+        } else {
+            (x, y)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rotation;
+
+    #[test]
+    fn offset_follows_rotation() {
+        let geometry = PanelGeometry::new(200, 400).with_offset(10, 20);
+
+        assert_eq!(geometry.offset(Rotation::Deg0.into()), (10, 20));
+        assert_eq!(geometry.offset(Rotation::Deg90.into()), (20, 110));
+        assert_eq!(geometry.offset(Rotation::Deg180.into()), (110, 60));
+        assert_eq!(geometry.offset(Rotation::Deg270.into()), (60, 10));
+    }
+
+    #[test]
+    fn size_swaps_with_rows_and_columns() {
+        let geometry = PanelGeometry::new(200, 400);
+
+        assert_eq!(geometry.size(Rotation::Deg0.into()), (200, 400));
+        assert_eq!(geometry.size(Rotation::Deg90.into()), (400, 200));
+        assert_eq!(geometry.size(Rotation::Deg180.into()), (200, 400));
+        assert_eq!(geometry.size(Rotation::Deg270.into()), (400, 200));
+    }
+
+    #[test]
+    fn is_valid_requires_visible_area_inside_gram() {
+        assert!(PanelGeometry::default().is_valid());
+        assert!(PanelGeometry::new(200, 400).with_offset(120, 80).is_valid());
+
+        assert!(!PanelGeometry::new(0, 480).is_valid());
+        assert!(!PanelGeometry::new(320, 0).is_valid());
+        assert!(!PanelGeometry::new(200, 400).with_offset(121, 0).is_valid());
+        assert!(!PanelGeometry::new(200, 400).with_offset(0, 81).is_valid());
+        assert!(!PanelGeometry::new(200, 400).with_offset(u16::MAX, 0).is_valid());
+        assert!(!PanelGeometry::new(320, 480).with_gram_size(240, 320).is_valid());
+    }
+}
@@ -1,40 +1,241 @@
-use embedded_graphics_core::pixelcolor::Rgb565;
-use embedded_graphics_core::prelude::{DrawTarget, IntoStorage, Point, Size};
+use core::marker::PhantomData;
+
+use embedded_graphics_core::pixelcolor::{Rgb565, Rgb888};
+use embedded_graphics_core::prelude::{DrawTarget, PixelColor, Point, Size};
 use embedded_graphics_core::{
-    pixelcolor::raw::{RawData, RawU16},
-    primitives::Rectangle,
+    pixelcolor::raw::{RawData, RawU16, RawU24},
+    primitives::{PointsIter, Rectangle},
 };
 use embedded_graphics_core::{prelude::OriginDimensions, Pixel};
 
 use embedded_hal::digital::v2::OutputPin;
 
-use crate::{Error, Orientation, ST7796};
+use crate::{rgb565_to_idle, rgb888_to_idle, Backlight, DisplayMode, Error, Madctl, ST7796};
 use display_interface::WriteOnlyDataCommand;
 
-impl<DI, RST, BL, PinE> ST7796<DI, RST, BL>
+/// Colors buffered by the Rgb565 DrawTarget for one slice transfer.
+const PIXEL_BUFFER_LEN: usize = 256;
+
+impl<DI, RST, BL, RstE, BlE> ST7796<DI, RST, BL>
 where
     DI: WriteOnlyDataCommand,
-    RST: OutputPin<Error = PinE>,
-    BL: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
 {
-    /// Returns the bounding box for the entire framebuffer.
-    fn framebuffer_bounding_box(&self) -> Rectangle {
-        let size = match self.orientation {
-            Orientation::Portrait | Orientation::PortraitSwapped => Size::new(320, 480),
-            Orientation::Landscape | Orientation::LandscapeSwapped => Size::new(480, 320),
+    /// Returns the bounding box for the visible part of the framebuffer.
+    pub(crate) fn framebuffer_bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.size())
+    }
+
+    ///
+    /// Returns the part of the display that is currently driven, in drawing
+    /// coordinates. This is the whole display in normal mode and the
+    /// partial area in partial mode.
+    ///
+    pub fn visible_area(&self) -> Rectangle {
+        let bounding_box = self.framebuffer_bounding_box();
+        let (start_row, end_row) = match self.cache.display_mode {
+            DisplayMode::Normal => return bounding_box,
+            DisplayMode::Partial { start_row, end_row } => (start_row, end_row),
+        };
+
+        // frame memory rows to panel rows
+        let (_, offset_y) = self.cache.geometry.offset(Madctl::new());
+        let (_, height) = self.cache.geometry.visible_size();
+        let first = i32::from(start_row) - i32::from(offset_y);
+        let last = i32::from(end_row) - i32::from(offset_y);
+        let (first, last) = if self.cache.madctl.mirror_y() {
+            (i32::from(height) - 1 - last, i32::from(height) - 1 - first)
+        } else {
+            (first, last)
         };
+        let length = (last - first + 1) as u32;
+
+        let rows = if self.cache.madctl.swap_xy() {
+            Rectangle::new(Point::new(first, 0), Size::new(length, bounding_box.size.height))
+        } else {
+            Rectangle::new(Point::new(0, first), Size::new(bounding_box.size.width, length))
+        };
+
+        rows.intersection(&bounding_box)
+    }
+
+    ///
+    /// Makes the DrawTargets reduce colors to the 8 colors of idle mode
+    /// while it is on, so the frame memory holds what the panel shows.
+    ///
+    pub fn set_idle_quantization(&mut self, enabled: bool) {
+        self.idle_quantization = enabled;
+    }
+
+    /// Returns true if drawn colors are reduced to the idle mode colors.
+    pub(crate) fn quantize_idle(&self) -> bool {
+        self.cache.idle_mode && self.idle_quantization
+    }
+
+    /// Private method:Sends colors after `start_pixels`, copied to a buffer so each chunk is one slice.
+    fn send_buffered(&mut self, mut colors: impl Iterator<Item = u16>) -> Result<(), Error<RstE, BlE>> {
+        let mut buffer = [0u16; PIXEL_BUFFER_LEN];
+        loop {
+            let mut len = 0;
+            for (slot, color) in buffer.iter_mut().zip(&mut colors) {
+                *slot = color;
+                len += 1;
+            }
+            if len == 0 {
+                return Ok(());
+            }
+
+            self.send_pixels(&mut buffer[..len])?;
+            if len < PIXEL_BUFFER_LEN {
+                return Ok(());
+            }
+        }
+    }
+
+    ///
+    /// Returns a DrawTarget for colors deeper than Rgb565, such as `Rgb666`
+    /// or `Rgb888`, to be used with the 18 and 24 bit pixel formats.
+    ///
+    pub fn deep_color<C>(&mut self) -> DeepColor<'_, DI, RST, BL, C>
+    where
+        C: PixelColor + Into<Rgb888>,
+    {
+        DeepColor {
+            display: self,
+            color: PhantomData,
+        }
+    }
+}
 
-        Rectangle::new(Point::zero(), size)
+///
+/// DrawTarget sending colors as 24 bit values, see `ST7796::deep_color`.
+///
+pub struct DeepColor<'a, DI, RST, BL, C>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    display: &'a mut ST7796<DI, RST, BL>,
+    color: PhantomData<C>,
+}
+
+/// Converts a color to the `0x00RRGGBB` value taken by `set_pixels_rgb888`.
+fn to_rgb888<C: Into<Rgb888>>(color: C, idle: bool) -> u32 {
+    let color = RawU24::from(color.into()).into_inner();
+    if idle {
+        rgb888_to_idle(color)
+    } else {
+        color
+    }
+}
+
+/// Converts a color to the Rgb565 value taken by `set_pixels`.
+pub(crate) fn to_rgb565(color: Rgb565, idle: bool) -> u16 {
+    let color = RawU16::from(color).into_inner();
+    if idle {
+        rgb565_to_idle(color)
+    } else {
+        color
     }
 }
 
-impl<DI, RST, BL, PinE> DrawTarget for ST7796<DI, RST, BL>
+impl<'a, DI, RST, BL, C, RstE, BlE> DrawTarget for DeepColor<'a, DI, RST, BL, C>
 where
     DI: WriteOnlyDataCommand,
-    RST: OutputPin<Error = PinE>,
-    BL: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+    C: PixelColor + Into<Rgb888>,
 {
-    type Error = Error<PinE>;
+    type Error = Error<RstE, BlE>;
+    type Color = C;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounding_box = self.display.framebuffer_bounding_box();
+        let idle = self.display.quantize_idle();
+        for Pixel(point, color) in pixels.into_iter().filter(|p| bounding_box.contains(p.0)) {
+            let x = point.x as u16;
+            let y = point.y as u16;
+
+            self.display
+                .set_pixels_rgb888(x, y, x, y, core::iter::once(to_rgb888(color, idle)))?;
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let drawable_area = area.intersection(&self.display.framebuffer_bounding_box());
+        let idle = self.display.quantize_idle();
+
+        if let Some(bottom_right) = drawable_area.bottom_right() {
+            let mut colors = area
+                .points()
+                .zip(colors)
+                .filter(|(point, _)| drawable_area.contains(*point))
+                .map(|(_, color)| to_rgb888(color, idle));
+
+            let sx = drawable_area.top_left.x as u16;
+            let sy = drawable_area.top_left.y as u16;
+            let ex = bottom_right.x as u16;
+            let ey = bottom_right.y as u16;
+            self.display.set_pixels_rgb888(sx, sy, ex, ey, &mut colors)
+        } else {
+            // nothing to draw
+            Ok(())
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.display.framebuffer_bounding_box());
+
+        if let Some(bottom_right) = area.bottom_right() {
+            let count = area.size.width * area.size.height;
+            let color = to_rgb888(color, self.display.quantize_idle());
+            let colors = core::iter::repeat(color).take(count as usize);
+
+            let sx = area.top_left.x as u16;
+            let sy = area.top_left.y as u16;
+            let ex = bottom_right.x as u16;
+            let ey = bottom_right.y as u16;
+            self.display.set_pixels_rgb888(sx, sy, ex, ey, colors)
+        } else {
+            // nothing to draw
+            Ok(())
+        }
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let bounding_box = self.display.framebuffer_bounding_box();
+        self.fill_solid(&bounding_box, color)
+    }
+}
+
+impl<'a, DI, RST, BL, C, RstE, BlE> OriginDimensions for DeepColor<'a, DI, RST, BL, C>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    fn size(&self) -> Size {
+        self.display.size()
+    }
+}
+
+impl<DI, RST, BL, RstE, BlE> DrawTarget for ST7796<DI, RST, BL>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    type Error = Error<RstE, BlE>;
     type Color = Rgb565;
 
     #[cfg(not(feature = "batch"))]
@@ -42,8 +243,10 @@ where
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        for pixel in pixels {
-            let color = RawU16::from(pixel.1).into_inner();
+        let bounding_box = self.framebuffer_bounding_box();
+        let idle = self.quantize_idle();
+        for pixel in pixels.into_iter().filter(|p| bounding_box.contains(p.0)) {
+            let color = to_rgb565(pixel.1, idle);
             let x = pixel.0.x as u16;
             let y = pixel.0.y as u16;
 
@@ -67,23 +270,40 @@ where
     where
         I: IntoIterator<Item = Self::Color>,
     {
-        if let Some(bottom_right) = area.bottom_right() {
-            let mut count = 0u32;
-            let max = area.size.width * area.size.height;
-
-            let mut colors = colors
-                .into_iter()
-                .take_while(|_| {
-                    count += 1;
-                    count <= max
-                })
-                .map(|color| RawU16::from(color).into_inner());
+        let drawable_area = area.intersection(&self.framebuffer_bounding_box());
+        let idle = self.quantize_idle();
 
-            let sx = area.top_left.x as u16;
-            let sy = area.top_left.y as u16;
+        if let Some(bottom_right) = drawable_area.bottom_right() {
+            let sx = drawable_area.top_left.x as u16;
+            let sy = drawable_area.top_left.y as u16;
             let ex = bottom_right.x as u16;
             let ey = bottom_right.y as u16;
-            self.set_pixels(sx, sy, ex, ey, &mut colors)
+
+            if drawable_area == *area {
+                let mut count = 0u32;
+                let max = area.size.width * area.size.height;
+
+                let colors = colors
+                    .into_iter()
+                    .take_while(|_| {
+                        count += 1;
+                        count <= max
+                    })
+                    .map(|color| to_rgb565(color, idle));
+
+                self.start_pixels(sx, sy, ex, ey)?;
+                self.send_buffered(colors)
+            } else {
+                // only send the colors of the points inside the visible area
+                let colors = area
+                    .points()
+                    .zip(colors)
+                    .filter(|(point, _)| drawable_area.contains(*point))
+                    .map(|(_, color)| to_rgb565(color, idle));
+
+                self.start_pixels(sx, sy, ex, ey)?;
+                self.send_buffered(colors)
+            }
         } else {
             // nothing to draw
             Ok(())
@@ -94,19 +314,15 @@ where
         let area = area.intersection(&self.framebuffer_bounding_box());
 
         if let Some(bottom_right) = area.bottom_right() {
-            let mut count = 0u32;
-            let max = area.size.width * area.size.height;
-
-            let mut colors = core::iter::repeat(color.into_storage()).take_while(|_| {
-                count += 1;
-                count <= max
-            });
+            let count = area.size.width * area.size.height;
+            let color = to_rgb565(color, self.quantize_idle());
 
             let sx = area.top_left.x as u16;
             let sy = area.top_left.y as u16;
             let ex = bottom_right.x as u16;
             let ey = bottom_right.y as u16;
-            self.set_pixels(sx, sy, ex, ey, &mut colors)
+            self.start_pixels(sx, sy, ex, ey)?;
+            self.send_buffered(core::iter::repeat(color).take(count as usize))
         } else {
             // nothing to draw
             Ok(())
@@ -117,29 +333,23 @@ where
     where
         Self: Sized,
     {
-        let color16 = RawU16::from(color).into_inner();
-        const DIM1: u16 = 320;
-        const DIM2: u16 = 480;
-        let colors = (0..(DIM1 as u32 * DIM2 as u32)).map(|_| color16); // blank entire HW RAM contents
-
-        match self.orientation {
-            Orientation::Portrait | Orientation::PortraitSwapped => {
-                self.set_pixels(0, 0, DIM1 - 1, DIM2 - 1, colors)
-            }
-            Orientation::Landscape | Orientation::LandscapeSwapped => {
-                self.set_pixels(0, 0, DIM2 - 1, DIM1 - 1, colors)
-            }
-        }
+        let color16 = to_rgb565(color, self.quantize_idle());
+        let (width, height) = self.cache.geometry.size(self.cache.madctl);
+        let count = usize::from(width) * usize::from(height); // blank the visible area
+
+        self.start_pixels(0, 0, width - 1, height - 1)?;
+        self.send_buffered(core::iter::repeat(color16).take(count))
     }
 }
 
-impl<DI, RST, BL, PinE> OriginDimensions for ST7796<DI, RST, BL>
+impl<DI, RST, BL, RstE, BlE> OriginDimensions for ST7796<DI, RST, BL>
 where
     DI: WriteOnlyDataCommand,
-    RST: OutputPin<Error = PinE>,
-    BL: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
 {
     fn size(&self) -> Size {
-        Size::new(self.size_x.into(), self.size_y.into()) // visible area, not RAM-pixel size
+        let (width, height) = self.cache.geometry.size(self.cache.madctl);
+        Size::new(width.into(), height.into()) // visible area, not RAM-pixel size
     }
 }
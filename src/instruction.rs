@@ -1,15 +1,18 @@
 /// ST7796S instructions.
 #[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Command {
     NOP = 0x00,
     SWRESET = 0x01,
 
     RDDID = 0x04,
+    RDNUMED = 0x05, // Read Number of the Errors on DSI.
     RDDST = 0x09,
     RDMODE = 0x0A,
     RDMADCTL = 0x0B,
     RDPIXFMT = 0x0C,
     RDIMGFMT = 0x0D,
+    RDSIGMODE = 0x0E, // RDDSM: Read Display Signal Mode.
     RDSELFDIAG = 0x0F,
 
     SLPIN = 0x10,
@@ -18,7 +21,7 @@ pub enum Command {
     NORON = 0x13,
 
     INVOFF = 0x20,
-    INVON = 0x21, //   S_GAMMASET = 0x26,
+    INVON = 0x21,
     DISPOFF = 0x28,
     DISPON = 0x29,
 
@@ -29,38 +32,291 @@ pub enum Command {
 
     PTLAR = 0x30,
     VSCRDER = 0x33,
+    TEOFF = 0x34,
+    TEON = 0x35,
     MADCTL = 0x36,
     VSCRSADD = 0x37, // Vertical Scrolling Start Address.
+    IDMOFF = 0x38,   // Idle Mode Off.
+    IDMON = 0x39,    // Idle Mode On: 8 colors.
     PIXFMT = 0x3A,   // COLMOD: Pixel Format Set.
+    WRMEMC = 0x3C,   // Write Memory Continue (RAMWRC).
+    RDMEMC = 0x3E,   // Read Memory Continue.
+
+    STE = 0x44,   // Set Tear Scanline.
+    GSCAN = 0x45, // Get Scanline.
 
-    RGB_INTERFACE = 0xB0, // RGB Interface Signal Control.
+    WRDISBV = 0x51,  // Write Display Brightness.
+    RDDISBV = 0x52,  // Read Display Brightness Value.
+    WRCTRLD = 0x53,  // Write CTRL Display.
+    RDCTRLD = 0x54,  // Read CTRL Value Display.
+    WRCABC = 0x55,   // Write Content Adaptive Brightness Control.
+    RDCABC = 0x56,   // Read Content Adaptive Brightness Control.
+    WRCABCMB = 0x5E, // Write CABC Minimum Brightness.
+    RDCABCMB = 0x5F, // Read CABC Minimum Brightness.
+
+    RDFCS = 0xAA,  // Read First Checksum.
+    RDCFCS = 0xAF, // Read Continue Checksum.
+
+    RGB_INTERFACE = 0xB0, // IFMODE: Interface Mode Control.
     FRMCTR1 = 0xB1,
     FRMCTR2 = 0xB2,
     FRMCTR3 = 0xB3,
     INVCTR = 0xB4,
+    BPC = 0xB5,     // Blanking Porch Control.
     DFUNCTR = 0xB6, // Display Function Control.
-    TEOFF = 0x34,
-    TEON = 0x35,
-    VCMOFFSET= 0xC6,
+    EM = 0xB7,      // Entry Mode Set.
 
     PWCTR1 = 0xC0,
     PWCTR2 = 0xC1,
     PWCTR3 = 0xC2,
-    PWCTR4 = 0xC3,
-    PWCTR5 = 0xC4,
     VMCTR1 = 0xC5,
+    VCMOFFSET = 0xC6,
+
+    NVMADW = 0xD0,   // NVM Address/Data Write.
+    NVMBPROG = 0xD1, // NVM Byte Program.
+    NVMSTRD = 0xD2,  // NVM Status Read.
+    RDID4 = 0xD3,
 
     RDID1 = 0xDA,
     RDID2 = 0xDB,
     RDID3 = 0xDC,
-    RDID4 = 0xDD,
 
     GMCTRP1 = 0xE0,
     GMCTRN1 = 0xE1,
     DGCTR1 = 0xE2,
     DGCTR2 = 0xE3,
-    MAD_RGB = 0x08,
-    MAD_X_RIGHT = 0x40,
-    MAD_Y_UP = 0x80,
+    DOCA = 0xE8,  // Display Output Ctrl Adjust.
+    CSCON = 0xF0, // Command Set Control.
+}
+
+impl Command {
+    ///
+    /// Returns true for Command Table 2 registers, which are only accepted
+    /// while enabled with CSCON.
+    ///
+    pub fn is_command2(self) -> bool {
+        matches!(self as u8, 0xB0..=0xB7 | 0xC0..=0xC6 | 0xD0..=0xD3 | 0xE0..=0xE8)
+    }
+
+    ///
+    /// Returns true for the commands that leave the driver state (SWRESET,
+    /// SLPIN, SLPOUT and DISPOFF), only sent by the driver methods.
+    ///
+    pub(crate) fn changes_driver_state(self) -> bool {
+        matches!(self, Command::SWRESET | Command::SLPIN | Command::SLPOUT | Command::DISPOFF)
+    }
+}
+
+impl From<Command> for u8 {
+    fn from(command: Command) -> u8 {
+        command as u8
+    }
+}
+
+impl TryFrom<u8> for Command {
+    type Error = u8;
+
+    ///
+    /// Returns the command with code `code`, or the code if the ST7796S does not define it.
+    ///
+    fn try_from(code: u8) -> Result<Self, u8> {
+        Ok(match code {
+            0x00 => Command::NOP,
+            0x01 => Command::SWRESET,
+            0x04 => Command::RDDID,
+            0x05 => Command::RDNUMED,
+            0x09 => Command::RDDST,
+            0x0A => Command::RDMODE,
+            0x0B => Command::RDMADCTL,
+            0x0C => Command::RDPIXFMT,
+            0x0D => Command::RDIMGFMT,
+            0x0E => Command::RDSIGMODE,
+            0x0F => Command::RDSELFDIAG,
+            0x10 => Command::SLPIN,
+            0x11 => Command::SLPOUT,
+            0x12 => Command::PTLON,
+            0x13 => Command::NORON,
+            0x20 => Command::INVOFF,
+            0x21 => Command::INVON,
+            0x28 => Command::DISPOFF,
+            0x29 => Command::DISPON,
+            0x2A => Command::CASET,
+            0x2B => Command::RASET,
+            0x2C => Command::RAMWR,
+            0x2E => Command::RAMRD,
+            0x30 => Command::PTLAR,
+            0x33 => Command::VSCRDER,
+            0x34 => Command::TEOFF,
+            0x35 => Command::TEON,
+            0x36 => Command::MADCTL,
+            0x37 => Command::VSCRSADD,
+            0x38 => Command::IDMOFF,
+            0x39 => Command::IDMON,
+            0x3A => Command::PIXFMT,
+            0x3C => Command::WRMEMC,
+            0x3E => Command::RDMEMC,
+            0x44 => Command::STE,
+            0x45 => Command::GSCAN,
+            0x51 => Command::WRDISBV,
+            0x52 => Command::RDDISBV,
+            0x53 => Command::WRCTRLD,
+            0x54 => Command::RDCTRLD,
+            0x55 => Command::WRCABC,
+            0x56 => Command::RDCABC,
+            0x5E => Command::WRCABCMB,
+            0x5F => Command::RDCABCMB,
+            0xAA => Command::RDFCS,
+            0xAF => Command::RDCFCS,
+            0xB0 => Command::RGB_INTERFACE,
+            0xB1 => Command::FRMCTR1,
+            0xB2 => Command::FRMCTR2,
+            0xB3 => Command::FRMCTR3,
+            0xB4 => Command::INVCTR,
+            0xB5 => Command::BPC,
+            0xB6 => Command::DFUNCTR,
+            0xB7 => Command::EM,
+            0xC0 => Command::PWCTR1,
+            0xC1 => Command::PWCTR2,
+            0xC2 => Command::PWCTR3,
+            0xC5 => Command::VMCTR1,
+            0xC6 => Command::VCMOFFSET,
+            0xD0 => Command::NVMADW,
+            0xD1 => Command::NVMBPROG,
+            0xD2 => Command::NVMSTRD,
+            0xD3 => Command::RDID4,
+            0xDA => Command::RDID1,
+            0xDB => Command::RDID2,
+            0xDC => Command::RDID3,
+            0xE0 => Command::GMCTRP1,
+            0xE1 => Command::GMCTRN1,
+            0xE2 => Command::DGCTR1,
+            0xE3 => Command::DGCTR2,
+            0xE8 => Command::DOCA,
+            0xF0 => Command::CSCON,
+            _ => return Err(code),
+        })
+    }
+}
+
+///
+/// A register value with a fixed parameter layout, sent with [`Instruction::COMMAND`].
+///
+pub trait Instruction {
+    /// Command the parameters belong to.
+    const COMMAND: Command;
+
+    /// Parameter bytes, usually a byte array.
+    type Bytes: AsRef<[u8]>;
+
+    /// Returns the parameters in the order they are sent.
+    fn to_bytes(&self) -> Self::Bytes;
+}
+
+///
+/// Column address range (CASET), inclusive.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ColumnAddress {
+    pub start: u16,
+    pub end: u16,
 }
 
+impl Instruction for ColumnAddress {
+    const COMMAND: Command = Command::CASET;
+    type Bytes = [u8; 4];
+
+    fn to_bytes(&self) -> [u8; 4] {
+        let [sh, sl] = self.start.to_be_bytes();
+        let [eh, el] = self.end.to_be_bytes();
+        [sh, sl, eh, el]
+    }
+}
+
+///
+/// Row address range (RASET), inclusive.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RowAddress {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl Instruction for RowAddress {
+    const COMMAND: Command = Command::RASET;
+    type Bytes = [u8; 4];
+
+    fn to_bytes(&self) -> [u8; 4] {
+        let [sh, sl] = self.start.to_be_bytes();
+        let [eh, el] = self.end.to_be_bytes();
+        [sh, sl, eh, el]
+    }
+}
+
+///
+/// Partial area (PTLAR): frame memory rows driven in partial mode, inclusive.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PartialArea {
+    pub start_row: u16,
+    pub end_row: u16,
+}
+
+impl Instruction for PartialArea {
+    const COMMAND: Command = Command::PTLAR;
+    type Bytes = [u8; 4];
+
+    fn to_bytes(&self) -> [u8; 4] {
+        let [sh, sl] = self.start_row.to_be_bytes();
+        let [eh, el] = self.end_row.to_be_bytes();
+        [sh, sl, eh, el]
+    }
+}
+
+///
+/// Vertical scroll start address (VSCSAD): the frame memory line shown
+/// at the top of the scrolling area.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ScrollStartAddress(pub u16);
+
+impl Instruction for ScrollStartAddress {
+    const COMMAND: Command = Command::VSCRSADD;
+    type Bytes = [u8; 2];
+
+    fn to_bytes(&self) -> [u8; 2] {
+        self.0.to_be_bytes()
+    }
+}
+
+///
+/// Tearing effect line on (TEON).
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TearingEffectLine {
+    /// Output horizontal blanking as well as vertical blanking (TEM).
+    pub horizontal: bool,
+}
+
+impl Instruction for TearingEffectLine {
+    const COMMAND: Command = Command::TEON;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [u8::from(self.horizontal)]
+    }
+}
+
+///
+/// Set tear scanline (STE): the TE output goes active when the display reaches this line.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TearScanline(pub u16);
+
+impl Instruction for TearScanline {
+    const COMMAND: Command = Command::STE;
+    type Bytes = [u8; 2];
+
+    fn to_bytes(&self) -> [u8; 2] {
+        self.0.to_be_bytes()
+    }
+}
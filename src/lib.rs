@@ -5,13 +5,60 @@
 
 //! This crate provides a ST7796S driver to connect to TFT displays.
 
+pub mod backlight;
+pub mod brightness;
+pub mod builder;
+pub mod config;
+pub mod diagnostics;
+pub mod display_function;
+pub mod frame_rate;
+pub mod gamma;
+pub mod geometry;
 pub mod instruction;
+pub mod madctl;
+pub mod power;
+mod raw;
+pub mod read;
+pub mod scroll;
+mod sequence;
+pub mod state;
 
-use crate::instruction::Command;
-use core::iter::once;
+pub use crate::backlight::{Backlight, DcsBacklight, GpioBacklight, PwmBacklight};
+pub use crate::brightness::{
+    AdaptiveBrightness, Brightness, BrightnessControl, CabcMode, ColorEnhancement, MinimumBrightness,
+};
+pub use crate::builder::Builder;
+pub use crate::config::{InitConfig, PixelFormat};
+pub use crate::diagnostics::{DisplayId, DisplayStatus, PowerMode, SelfDiagnostic};
+pub use crate::display_function::{
+    ColorExpansion, DisplayFunction, DrivingLines, EntryMode, GateOutput, InterfaceMode, IntervalScan,
+    NonDisplayScan, NonDisplaySource, OutputTiming,
+};
+pub use crate::frame_rate::{
+    BlankingPorch, ClockDivider, FrameRate, FrameRates, IdleFrameRate, InversionMode, NormalFrameRate,
+    PartialFrameRate,
+};
+pub use crate::gamma::{Gamma, GammaCurve, NegativeGamma, PositiveGamma};
+pub use crate::geometry::PanelGeometry;
+pub use crate::madctl::{Madctl, Rotation};
+pub use crate::power::{
+    DrivingCurrent, DrivingCurrents, DrivingVoltages, PowerSettings, Vcom, VcomOffset, Vrh,
+};
+pub use crate::raw::UnknownState;
+use crate::raw::CachedState;
+use crate::sequence::{Params, Step};
+pub use crate::read::ReadWriteDataCommand;
+pub use crate::scroll::{ScrollRegion, ScrollState};
+pub use crate::state::{Ready, Sleeping, Uninit};
+use crate::instruction::{
+    Command, Instruction, PartialArea, ScrollStartAddress, TearScanline, TearingEffectLine,
+};
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::iter::{once, repeat};
 
-use display_interface::DataFormat::{U16BEIter, U8Iter};
-use display_interface::WriteOnlyDataCommand;
+use display_interface::DataFormat::{U16BEIter, U16BE, U8Iter, U8};
+use display_interface::{DisplayError, WriteOnlyDataCommand};
 use embedded_hal::blocking::delay::DelayUs;
 use embedded_hal::digital::v2::OutputPin;
 
@@ -19,18 +66,39 @@ use embedded_hal::digital::v2::OutputPin;
 #[cfg(feature = "graphics")]
 mod graphics;
 
+#[cfg(feature = "graphics")]
+pub use crate::graphics::DeepColor;
+
 #[cfg(feature = "batch")]
 mod batch;
 
+#[cfg(feature = "async")]
+mod asynch;
+
+#[cfg(feature = "async")]
+pub use crate::asynch::ST7796Async;
+
+#[cfg(feature = "eh1")]
+mod eh1;
+
+#[cfg(feature = "eh1")]
+pub use crate::eh1::{Eh1Delay, Eh1Pin};
+
+#[cfg(test)]
+mod mock;
+
 ///
 /// ST7796S driver to connect with TFT Display.
 /// Using SPI protocol.
 ///
-pub struct ST7796<DI, RST, BL>
+/// `S` is the driver state, see [`state`]: `new` returns an [`Uninit`]
+/// driver, only a [`Ready`] driver draws.
+///
+pub struct ST7796<DI, RST, BL, S = Ready>
 where
     DI: WriteOnlyDataCommand,
     RST: OutputPin,
-    BL: OutputPin,
+    BL: Backlight,
 {
     // Display Interface.
     di: DI,
@@ -38,26 +106,29 @@ where
     rst: Option<RST>,
     // Backlight Pin.
     bl: Option<BL>,
-    // Visible size (x, y)
-    size_x: u16,
-    size_y: u16,
-    // current orientation.
-    orientation: Orientation,
+    // reduce drawn colors to the idle mode colors while idle.
+    #[cfg(feature = "graphics")]
+    idle_quantization: bool,
+    // display state, kept in step with the commands sent.
+    cache: CachedState,
+    // Uninit, Ready or Sleeping.
+    state: PhantomData<S>,
 }
 
 /// Display Orientation to switch between 
 /// Landscape, Portrait Modes.
+/// Use [`Madctl`] for mirroring, BGR and refresh order settings.
 #[repr(u8)] 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub enum Orientation {
-    //D7=0,D6=0,D5=0,D4=0,D3=0,D2=0,D1=0,D0=0 (For More refer on Datasheet MADCTL pg.no:183)
-    Portrait = 0b0000_0000, // No Inverting. Writing on none of the pins 
-    //D7=0,D6=1,D5=1,D4=0,D3=0,D2=0,D1=0,D0=0
-    Landscape = 0b0110_0000, // Invert column and Row/column order.
-    //D7=1,D6=1,D5=0,D4=0,D3=0,D2=0,D1=0,D0=0
-    PortraitSwapped = 0b1100_0000, // Invert Row and column order.
-    //D7=1,D6=0,D5=1,D4=0,D3=0,D2=0,D1=0,D0=0
-    LandscapeSwapped = 0b1010_0000, // Invert Row and Row/column order.
+    //MY=0,MX=0,MV=0 (For More refer on Datasheet MADCTL pg.no:183)
+    Portrait = 0b0000_0000, // Normal address order.
+    //MY=0,MX=1,MV=1
+    Landscape = 0b0110_0000, // Reversed column order, Row/column exchange.
+    //MY=1,MX=1,MV=0
+    PortraitSwapped = 0b1100_0000, // Reversed row and column order.
+    //MY=1,MX=0,MV=1
+    LandscapeSwapped = 0b1010_0000, // Reversed row order, Row/column exchange.
 }
 
 /// Default Screen orientation set as 
@@ -82,6 +153,85 @@ pub enum TearingEffect {
     HorizontalVertical,
 }
 
+///
+/// Display Mode Setting.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DisplayMode {
+    /// The whole panel is driven (NORON).
+    Normal,
+    /// Only the frame memory rows `start_row..=end_row` are driven (PTLON),
+    /// the rest of the panel shows the non-display area color.
+    Partial { start_row: u16, end_row: u16 },
+}
+
+///
+/// Sleep and display state.
+/// After power on or a reset the display sleeps with its output off.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PowerState {
+    /// Sleep in mode (SLPIN): DC/DC converter, oscillator and panel scanning stopped.
+    pub sleeping: bool,
+    /// Frame memory output enabled (DISPON).
+    pub display_on: bool,
+}
+
+impl Default for PowerState {
+    fn default() -> Self {
+        Self {
+            sleeping: true,
+            display_on: false,
+        }
+    }
+}
+
+/// Wait after SLPOUT before SLPIN may be sent.
+const SLEEP_OUT_TO_SLEEP_IN_US: u32 = 120_000;
+
+/// Wait after SLPIN or SLPOUT before the next command.
+const SLEEP_COMMAND_DELAY_US: u32 = 5_000;
+
+/// Wait after SWRESET before SLPOUT may be sent.
+const SOFT_RESET_TO_SLEEP_OUT_US: u32 = 120_000;
+
+///
+/// Placeholder for a reset or backlight pin that is not connected.
+///
+/// Setting it does nothing and cannot fail. The `new_without_*`
+/// constructors use it, so the missing pin needs no concrete type.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NoPin;
+
+impl OutputPin for NoPin {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl embedded_hal_1::digital::ErrorType for NoPin {
+    type Error = Infallible;
+}
+
+#[cfg(feature = "eh1")]
+impl embedded_hal_1::digital::OutputPin for NoPin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
 /// 
 /// Backlight State Setting.
 /// 
@@ -92,21 +242,38 @@ pub enum BacklightState {
 }
 
 ///
-/// Error Referring to its source (pins or SPI)
+/// Error Referring to its source (display interface, pins or arguments)
+///
+/// The reset and backlight pins may come from different sources, so each
+/// has its own error type; `BlE` defaults to the reset pin error type.
 ///
 #[derive(Debug)]
-pub enum Error<PinE> {
-    DisplayError,
-    Pin(PinE),
+pub enum Error<RstE, BlE = RstE> {
+    /// The display interface failed to send or receive.
+    Interface(DisplayError),
+    /// Setting the reset pin failed.
+    Reset(RstE),
+    /// Setting the backlight pin failed.
+    Backlight(BlE),
+    /// An argument is outside the range the display accepts.
+    OutOfBounds,
+    /// A configuration is inconsistent with the panel.
+    InvalidConfig,
+}
+
+impl<RstE, BlE> From<DisplayError> for Error<RstE, BlE> {
+    fn from(error: DisplayError) -> Self {
+        Error::Interface(error)
+    }
 }
  
 // Trait Implementation of ST7796.
 
-impl<DI, RST, BL, PinE> ST7796<DI, RST, BL>
+impl<DI, RST, BL, RstE, BlE> ST7796<DI, RST, BL, Uninit>
 where
     DI: WriteOnlyDataCommand,
-    RST: OutputPin<Error=PinE>,
-    BL: OutputPin<Error=PinE>,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
 {
     ///
     /// Creates a new ST7796 driver instance
@@ -115,54 +282,82 @@ where
     ///
     /// * `di` - Display Interface to communicate with display.
     /// * `rst` - Display hard reset pin.
-    /// * `bl` - backlight pin.
-    /// * `size_x` - x axis resolution of the display in pixels.
-    /// * `size_y` - y axis resolution of the display in pixels.
+    /// * `bl` - backlight, such as a [`GpioBacklight`].
+    /// * `size_x` - x axis resolution of the display in pixels, in portrait orientation.
+    /// * `size_y` - y axis resolution of the display in pixels, in portrait orientation.
     ///
     pub fn new(di: DI, rst: Option<RST>, bl: Option<BL>, size_x: u16, size_y: u16) -> Self {
+        Self::with_geometry(di, rst, bl, PanelGeometry::new(size_x, size_y))
+    }
+
+    ///
+    /// Creates a new ST7796 driver instance for a panel that does not
+    /// cover the whole 320x480 GRAM.
+    ///
+    /// # Arguments.
+    ///
+    /// * `di` - Display Interface to communicate with display.
+    /// * `rst` - Display hard reset pin.
+    /// * `bl` - backlight, such as a [`GpioBacklight`].
+    /// * `geometry` - visible size and GRAM offset of the panel.
+    ///
+    pub fn with_geometry(di: DI, rst: Option<RST>, bl: Option<BL>, geometry: PanelGeometry) -> Self {
         Self {
             di, rst, bl,
-            size_x, size_y,
-            orientation: Orientation::default(),
+            #[cfg(feature = "graphics")]
+            idle_quantization: false,
+            cache: CachedState::new(geometry),
+            state: PhantomData,
         }
+    }
 
+    /// 
+    /// Runs commands to intialize the display, using [`InitConfig::default`],
+    /// returning the ready driver.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay_source` - mutable reference to a delay provider.
+    ///
+    pub fn init(
+        self,
+        delay_source: &mut impl DelayUs<u32>,
+    ) -> Result<ST7796<DI, RST, BL, Ready>, (Self, Error<RstE, BlE>)> {
+        self.init_with(&InitConfig::default(), delay_source)
     }
 
     /// 
-    /// Runs commands to intialize the display
+    /// Runs the bring-up sequence described by `config`, returning the ready driver.
+    /// Fails with [`Error::InvalidConfig`] before touching the display if the
+    /// panel geometry is not valid, see [`PanelGeometry::is_valid`], the
+    /// scroll region or display function of `config` do not fit the panel,
+    /// or its commands would leave the driver state, see [`InitConfig::with_commands`].
+    /// On failure the uninitialized driver is handed back with the error, so
+    /// init can be retried or the pins released.
+    ///
+    /// Without a reset pin the display is reset with SWRESET only: init
+    /// first waits 120ms for a sleep out sequence that may still be running,
+    /// and waits at least 120ms after SWRESET before SLPOUT.
+    /// The backlight is only set once the display is on.
     ///
     /// # Arguments
     ///
+    /// * `config` - the init sequence to run.
     /// * `delay_source` - mutable reference to a delay provider.
     ///
-    pub fn init(&mut self, delay_source: &mut impl DelayUs<u32>) -> Result<(), Error<PinE>> {
-        self.hard_reset(delay_source)?;
-        if let Some(bl) = self.bl.as_mut() {
-            bl.set_low().map_err(Error::Pin)?;
-            delay_source.delay_us(10_000);
-            bl.set_high().map_err(Error::Pin)?;
+    pub fn init_with(
+        mut self,
+        config: &InitConfig,
+        delay_source: &mut impl DelayUs<u32>,
+    ) -> Result<ST7796<DI, RST, BL, Ready>, (Self, Error<RstE, BlE>)> {
+        if !config.is_valid(&self.cache.geometry) {
+            return Err((self, Error::InvalidConfig));
         }
 
-        //* TODO multiple write_commands *//
-        self.write_command(Command::SWRESET)?; // Reset display
-        delay_source.delay_us(150_000);
-        self.write_command(Command::SLPOUT)?; // Turn OFF Sleep
-        delay_source.delay_us(10_000);
-        self.write_command(Command::INVOFF)?; // Turn OFF Invert
-        self.write_command(Command::VSCRDER)?; // Vertical Scroll definition
-        self.write_data(&[0u8, 0u8, 0x1Eu8, 0u8, 0u8, 0u8])?; // 0 TFA, 480 VSA, 0 BFA
-        self.write_command(Command::MADCTL)?; // left -> right, bottom -> top RGB
-        self.write_data(&[0b0000_0000])?;
-        self.write_command(Command::PIXFMT)?; // 16bit 65k colors
-        self.write_data(&[0b0101_0101])?;
-        self.write_command(Command::INVON)?; // Turn ON Invert
-        delay_source.delay_us(10_000);
-        self.write_command(Command::NORON)?; // Turn ON Display
-        delay_source.delay_us(10_000);
-        self.write_command(Command::DISPON)?; // Turn ON Display
-        delay_source.delay_us(10_000);
-
-        Ok(())
+        match self.bring_up(config, delay_source) {
+            Ok(()) => Ok(self.into_state()),
+            Err(error) => Err((self, error)),
+        }
     }
 
     ///
@@ -172,30 +367,52 @@ where
     ///
     /// * `delay_source` - mutable reference to a delay provider
     ///
-    pub fn hard_reset(&mut self, delay_source: &mut impl DelayUs<u32>) -> Result<(), Error<PinE>> {
+    pub fn hard_reset(&mut self, delay_source: &mut impl DelayUs<u32>) -> Result<(), Error<RstE, BlE>> {
         if let Some(rst) = self.rst.as_mut() {
-            rst.set_high().map_err(Error::Pin)?;
+            rst.set_high().map_err(Error::Reset)?;
             delay_source.delay_us(10); // ensure the pin change will get registered
-            rst.set_low().map_err(Error::Pin)?;
+            rst.set_low().map_err(Error::Reset)?;
             delay_source.delay_us(10); // ensure the pin change will get registered
-            rst.set_high().map_err(Error::Pin)?;
+            rst.set_high().map_err(Error::Reset)?;
             delay_source.delay_us(10); // ensure the pin change will get registered
+            self.cache.reset(true);
+        }
+
+        Ok(())
+    }
+
+    // --- Private Functions --- //
+
+    /// Private method:Resets the display and runs the init sequence of `config`.
+    fn bring_up(&mut self, config: &InitConfig, delay_source: &mut impl DelayUs<u32>) -> Result<(), Error<RstE, BlE>> {
+        let hard_reset = self.rst.is_some();
+        self.hard_reset(delay_source)?;
+        self.run(sequence::init(config, hard_reset), delay_source)?;
+        if let (Some(bl), Some(brightness)) = (self.bl.as_mut(), config.backlight) {
+            bl.set_brightness(brightness).map_err(Error::Backlight)?;
         }
 
         Ok(())
     }
+}
 
+impl<DI, RST, BL, S, RstE, BlE> ST7796<DI, RST, BL, S>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
     /// 
-    /// Method to set the state of BacklightState
+    /// Method to set the state of BacklightState: full brightness or off.
     ///
     pub fn set_backlight(
         &mut self, state: BacklightState, 
         delay_source: &mut impl DelayUs<u32>
-    ) -> Result<(), Error<PinE>> {
+    ) -> Result<(), Error<RstE, BlE>> {
         if let Some(bl) = self.bl.as_mut() {
             match state {
-                BacklightState::ON => bl.set_high().map_err(Error::Pin)?,
-                BacklightState::OFF => bl.set_low().map_err(Error::Pin)?,
+                BacklightState::ON => bl.set_brightness(u8::MAX).map_err(Error::Backlight)?,
+                BacklightState::OFF => bl.set_brightness(0).map_err(Error::Backlight)?,
             }
             delay_source.delay_us(10);
         }
@@ -204,78 +421,87 @@ where
     }
 
     ///
-    /// Returns the current state of display orientation.
+    /// Returns the backlight, for brightness changes and fades.
     ///
-    pub fn orientation(&self) -> Orientation {
-        self.orientation
+    pub fn backlight(&mut self) -> Option<&mut BL> {
+        self.bl.as_mut()
     }
-    
+
     ///
-    /// Sets a new state of display orientation.
+    /// Returns the current sleep and display state.
     ///
-    pub fn set_orientation(&mut self, orientation: Orientation) -> Result<(), Error<PinE>> {
-        self.write_command(Command::MADCTL)?;
-        self.write_data(&[orientation as u8])?;
-        self.orientation = orientation;
+    pub fn power_state(&self) -> PowerState {
+        self.cache.power_state
+    }
 
-        Ok(())
+    ///
+    /// Returns the panel geometry.
+    ///
+    pub fn geometry(&self) -> PanelGeometry {
+        self.cache.geometry
     }
-    
-    /// 
-    /// Sets a pixel color at the given coords.
+
     ///
-    /// # Arguments
+    /// Returns the current state of display orientation.
     ///
-    /// * `x` - X  coordinate.
-    /// * `y` - Y  coordinate.
-    /// * `color` - the Rgb565 color value
+    pub fn orientation(&self) -> Madctl {
+        self.cache.madctl
+    }
+
     ///
-    pub fn set_pixel(&mut self, x: u16, y:u16, color: u16) -> Result<(), Error<PinE>> {
-        self.set_address_window(x, y, x, y)?;
-        self.write_command(Command::RAMWR)?;
-        self.di
-            .send_data(U16BEIter(&mut once(color)))
-            .map_err(|_| Error::DisplayError)?;
+    /// Returns the current interface pixel format.
+    ///
+    pub fn pixel_format(&self) -> PixelFormat {
+        self.cache.pixel_format
+    }
 
-        Ok(())
+    ///
+    /// Returns the current frame rate settings.
+    ///
+    pub fn frame_rates(&self) -> FrameRates {
+        self.cache.frame_rates
     }
-    
+
     ///
-    /// Sets pixel colors in given rectangle bounds.
+    /// Returns the frame rate of the current display mode, in millihertz.
     ///
-    /// # Arguments
+    pub fn frame_rate_millihertz(&self) -> u32 {
+        if self.cache.idle_mode {
+            return self.cache.frame_rates.idle.millihertz();
+        }
+
+        match self.cache.display_mode {
+            DisplayMode::Normal => self.cache.frame_rates.normal.millihertz(),
+            DisplayMode::Partial { .. } => self.cache.frame_rates.partial.millihertz(),
+        }
+    }
+
     ///
-    /// * `sx` - x coordinate start
-    /// * `sy` - y coordinate start
-    /// * `ex` - x coordinate end
-    /// * `ey` - y coordinate end
-    /// * `colors` - anything that can provide `IntoIterator<Item = u16>` to iterate over pixel data
+    /// Returns the current vertical scroll areas and offset.
     ///
-    pub fn set_pixels<T>(
-        &mut self,
-        sx: u16, sy: u16,
-        ex: u16, ey: u16,
-        colors: T,
-    ) -> Result<(), Error<PinE>>
-    where
-        T: IntoIterator<Item = u16>,
-    {
-        self.set_address_window(sx, sy, ex, ey)?;
-        self.write_command(Command::RAMWR)?;
-        self.di
-            .send_data(U16BEIter(&mut colors.into_iter()))
-            .map_err(|_| Error::DisplayError)
+    pub fn scroll_state(&self) -> ScrollState {
+        self.cache.scroll
     }
-    
+
     ///
-    /// Sets scroll offset "shifting" the displayed picture
-    /// # Arguments
+    /// Returns the current display mode.
+    ///
+    pub fn display_mode(&self) -> DisplayMode {
+        self.cache.display_mode
+    }
+
+    ///
+    /// Returns true if display inversion is on.
+    ///
+    pub fn is_inverted(&self) -> bool {
+        self.cache.inverted
+    }
+
     ///
-    /// * `offset` - scroll offset in pixels
+    /// Returns true if idle mode is on.
     ///
-    pub fn set_scroll_offset(&mut self, offset: u16) -> Result<(), Error<PinE>> {
-        self.write_command(Command::VSCRSADD)?;
-        self.write_data(&offset.to_be_bytes())
+    pub fn idle_mode(&self) -> bool {
+        self.cache.idle_mode
     }
 
     ///
@@ -286,56 +512,909 @@ where
         (self.di, self.rst, self.bl)
     }
 
+    /// Private method:Writing Data utilising the `send_commands` method of display_interface crate.
+    fn write_command(&mut self, command: Command) -> Result<(), Error<RstE, BlE>> {
+        self.di
+            .send_commands(U8Iter(&mut once(command as u8)))
+            .map_err(Error::Interface)?;
+
+        Ok(())
+    }
+
+    /// Private method:Writes a command byte followed by its parameters.
+    pub(crate) fn write_raw(&mut self, command: u8, params: &[u8]) -> Result<(), Error<RstE, BlE>> {
+        self.di
+            .send_commands(U8Iter(&mut once(command)))
+            .map_err(Error::Interface)?;
+        self.write_data(params)
+    }
+
+    /// Private method:Writing Data utilising the `send_data` method of display_interface crate.
+    /// Nothing is sent for a command without parameters.
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<RstE, BlE>> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        self.di
+            .send_data(U8Iter(&mut data.iter().cloned()))
+            .map_err(Error::Interface)?;
+
+        Ok(())
+    }
+
+    /// Private method:Sends commands built by `sequence`, keeping the cached state in step.
+    fn write_all<'a>(&mut self, writes: impl IntoIterator<Item = (Command, Params<'a>)>) -> Result<(), Error<RstE, BlE>> {
+        for (command, params) in writes {
+            self.write_command(command)?;
+            self.write_data(params.as_slice())?;
+            self.cache.sync(command, params.as_slice());
+        }
+
+        Ok(())
+    }
+
+    /// Private method:Runs a sequence built by `sequence`, waiting where it says.
+    fn run<'a>(
+        &mut self,
+        steps: impl IntoIterator<Item = Step<'a>>,
+        delay_source: &mut impl DelayUs<u32>,
+    ) -> Result<(), Error<RstE, BlE>> {
+        for step in steps {
+            match step {
+                Step::Write(command, params) => {
+                    self.write_command(command)?;
+                    self.write_data(params.as_slice())?;
+                }
+                Step::Wait(us) => delay_source.delay_us(us),
+            }
+            self.cache.apply(&step);
+        }
+
+        Ok(())
+    }
+
+    /// Private method:Changes the state type parameter, keeping all fields.
+    fn into_state<T>(self) -> ST7796<DI, RST, BL, T> {
+        ST7796 {
+            di: self.di,
+            rst: self.rst,
+            bl: self.bl,
+            #[cfg(feature = "graphics")]
+            idle_quantization: self.idle_quantization,
+            cache: self.cache,
+            state: PhantomData,
+        }
+    }
+}
+
+impl<DI, RST, BL, RstE, BlE> ST7796<DI, RST, BL, Sleeping>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
     ///
-    /// Configures the tearing effect output.
+    /// Leaves sleep mode (SLPOUT), returning the ready driver.
     ///
-    pub fn set_tearing_effect(&mut self, tearing_effect: TearingEffect) -> Result<(), Error<PinE>> {
-        match tearing_effect {
-            TearingEffect::Off => self.write_command(Command::TEOFF),
+    /// Waits 5ms after SLPOUT; the rest of the 120ms the display needs
+    /// before it may sleep again is waited by [`ST7796::sleep`].
+    /// On failure the sleeping driver is handed back with the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay_source` - mutable reference to a delay provider.
+    ///
+    pub fn wake(
+        mut self,
+        delay_source: &mut impl DelayUs<u32>,
+    ) -> Result<ST7796<DI, RST, BL, Ready>, (Self, Error<RstE, BlE>)> {
+        match self.run(sequence::wake(), delay_source) {
+            Ok(()) => Ok(self.into_state()),
+            Err(error) => Err((self, error)),
+        }
+    }
+}
 
-            TearingEffect::Vertical => {
-                self.write_command(Command::TEON)?;
-                self.write_data(&[0])
-            }
+impl<DI, RST, BL, RstE, BlE> ST7796<DI, RST, BL, Ready>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Enters sleep mode (SLPIN), returning the sleeping driver.
+    /// SLPIN is not sent again if the display already sleeps.
+    ///
+    /// The driver has no clock, so it assumes no time passed since the last
+    /// sleep out and waits out the rest of the 120ms first; use
+    /// [`ST7796::sleep_after`] when more time has passed. Waits 5ms after SLPIN.
+    /// On failure the ready driver is handed back with the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay_source` - mutable reference to a delay provider.
+    ///
+    pub fn sleep(
+        self,
+        delay_source: &mut impl DelayUs<u32>,
+    ) -> Result<ST7796<DI, RST, BL, Sleeping>, (Self, Error<RstE, BlE>)> {
+        self.sleep_after(0, delay_source)
+    }
 
-            TearingEffect::HorizontalVertical => {
-                self.write_command(Command::TEON)?;
-                self.write_data(&[1])
+    ///
+    /// Enters sleep mode (SLPIN) as [`ST7796::sleep`] does, counting
+    /// `elapsed_us` towards the 120ms the display needs after sleep out.
+    ///
+    /// # Arguments
+    ///
+    /// * `elapsed_us` - time passed since `init` or `wake` returned.
+    /// * `delay_source` - mutable reference to a delay provider.
+    ///
+    pub fn sleep_after(
+        mut self,
+        elapsed_us: u32,
+        delay_source: &mut impl DelayUs<u32>,
+    ) -> Result<ST7796<DI, RST, BL, Sleeping>, (Self, Error<RstE, BlE>)> {
+        if !self.cache.power_state.sleeping {
+            let wait_us = self.cache.sleep_in_wait_us.saturating_sub(elapsed_us);
+            if let Err(error) = self.run(sequence::sleep(wait_us), delay_source) {
+                return Err((self, error));
             }
         }
 
+        Ok(self.into_state())
     }
 
-    // --- Private Functions --- //
+    ///
+    /// Turns the display output off (DISPOFF); the frame memory is kept.
+    ///
+    pub fn display_off(&mut self) -> Result<(), Error<RstE, BlE>> {
+        self.write_command(Command::DISPOFF)?;
+        self.cache.power_state.display_on = false;
 
-    /// Private method:Writing Data utilising the `send_commands` method of display_interface crate.
-    fn write_command(&mut self, command: Command) -> Result<(), Error<PinE>> {
-        self.di
-            .send_commands(U8Iter(&mut once(command as u8)))
-            .map_err(|_| Error::DisplayError)?;
+        Ok(())
+    }
+
+    ///
+    /// Turns the display output on (DISPON).
+    ///
+    pub fn display_on(&mut self) -> Result<(), Error<RstE, BlE>> {
+        self.write_command(Command::DISPON)?;
+        self.cache.power_state.display_on = true;
 
         Ok(())
     }
 
-    /// Private method:Writing Data utilising the `send_data` method of display_interface crate.
-    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<PinE>> {
-        self.di
-            .send_data(U8Iter(&mut data.iter().cloned()))
-            .map_err(|_| Error::DisplayError)?;
+    ///
+    /// Sets a new state of display orientation.
+    /// Accepts an [`Orientation`], a [`Rotation`] or a full [`Madctl`] value.
+    ///
+    pub fn set_orientation(&mut self, orientation: impl Into<Madctl>) -> Result<(), Error<RstE, BlE>> {
+        let madctl = orientation.into();
+        self.write_register(&madctl)?;
+        self.cache.madctl = madctl;
 
         Ok(())
     }
 
-    /// Private method:Sets the address window for the display.
-    fn set_address_window(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), Error<PinE>> {
-        self.write_command(Command::CASET)?;
-        self.write_data(&sx.to_be_bytes())?;
-        self.write_data(&ex.to_be_bytes())?;
-        self.write_command(Command::RASET)?;
-        self.write_data(&sy.to_be_bytes())?;
-        self.write_data(&ey.to_be_bytes())?;
+    ///
+    /// Sets the interface pixel format.
+    /// Rgb565 colors passed to `set_pixel(s)` are expanded to 3 bytes when
+    /// an 18 or 24 bit format is active.
+    ///
+    pub fn set_pixel_format(&mut self, pixel_format: PixelFormat) -> Result<(), Error<RstE, BlE>> {
+        self.write_register(&pixel_format)?;
+        self.cache.pixel_format = pixel_format;
 
         Ok(())
     }
 
+    /// 
+    /// Sets a pixel color at the given coords.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X  coordinate.
+    /// * `y` - Y  coordinate.
+    /// * `color` - the Rgb565 color value
+    ///
+    pub fn set_pixel(&mut self, x: u16, y:u16, color: u16) -> Result<(), Error<RstE, BlE>> {
+        self.set_pixels(x, y, x, y, once(color))
+    }
+    
+    ///
+    /// Sets pixel colors in given rectangle bounds.
+    /// Fails with [`Error::OutOfBounds`] unless the rectangle is inside the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `colors` - anything that can provide `IntoIterator<Item = u16>` to iterate over pixel data
+    ///
+    pub fn set_pixels<T>(
+        &mut self,
+        sx: u16, sy: u16,
+        ex: u16, ey: u16,
+        colors: T,
+    ) -> Result<(), Error<RstE, BlE>>
+    where
+        T: IntoIterator<Item = u16>,
+    {
+        self.start_pixels(sx, sy, ex, ey)?;
+        match self.cache.pixel_format {
+            PixelFormat::Rgb565 => self
+                .di
+                .send_data(U16BEIter(&mut colors.into_iter()))
+                .map_err(Error::Interface),
+            PixelFormat::Rgb666 | PixelFormat::Rgb888 => self
+                .di
+                .send_data(U8Iter(&mut colors.into_iter().flat_map(rgb565_to_bytes)))
+                .map_err(Error::Interface),
+        }
+    }
+
+    ///
+    /// Sets 24 bit pixel colors in given rectangle bounds.
+    /// Colors are reduced to Rgb565 when the 16 bit format is active.
+    /// Fails with [`Error::OutOfBounds`] unless the rectangle is inside the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `colors` - anything that can provide `IntoIterator<Item = u32>` of `0x00RRGGBB` values
+    ///
+    pub fn set_pixels_rgb888<T>(
+        &mut self,
+        sx: u16, sy: u16,
+        ex: u16, ey: u16,
+        colors: T,
+    ) -> Result<(), Error<RstE, BlE>>
+    where
+        T: IntoIterator<Item = u32>,
+    {
+        self.start_pixels(sx, sy, ex, ey)?;
+        match self.cache.pixel_format {
+            PixelFormat::Rgb565 => self
+                .di
+                .send_data(U16BEIter(&mut colors.into_iter().map(rgb888_to_rgb565)))
+                .map_err(Error::Interface),
+            PixelFormat::Rgb666 | PixelFormat::Rgb888 => self
+                .di
+                .send_data(U8Iter(&mut colors.into_iter().flat_map(|c| {
+                    let [_, r, g, b] = c.to_be_bytes();
+                    [r, g, b]
+                })))
+                .map_err(Error::Interface),
+        }
+    }
+
+    ///
+    /// Sets pixel colors in given rectangle bounds from a buffer, passing it to
+    /// the display interface as one slice so it can be sent in a single transfer.
+    /// Colors are expanded to 3 bytes, without a slice, when an 18 or 24 bit format is active.
+    /// Fails with [`Error::OutOfBounds`] unless the rectangle is inside the display
+    /// and `colors` holds one color per pixel.
+    ///
+    /// The interface converts the colors to big endian in place, so on little
+    /// endian targets the buffer holds byte-swapped colors afterwards. Use
+    /// [`write_pixels_be`](Self::write_pixels_be) to keep a buffer unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `colors` - Rgb565 colors, row by row
+    ///
+    pub fn write_pixels(
+        &mut self,
+        sx: u16, sy: u16,
+        ex: u16, ey: u16,
+        colors: &mut [u16],
+    ) -> Result<(), Error<RstE, BlE>> {
+        if colors.len() != pixel_count(sx, sy, ex, ey) {
+            return Err(Error::OutOfBounds);
+        }
+
+        self.start_pixels(sx, sy, ex, ey)?;
+        self.send_pixels(colors)
+    }
+
+    ///
+    /// Sets pixel colors in given rectangle bounds from bytes already in the
+    /// interface pixel format: 2 big endian bytes per pixel in the 16 bit
+    /// format, 3 bytes otherwise. The bytes are passed as one slice and are
+    /// not modified.
+    /// Fails with [`Error::OutOfBounds`] unless the rectangle is inside the display
+    /// and `data` holds the bytes of one pixel per pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `data` - pixel bytes, row by row
+    ///
+    pub fn write_pixels_be(
+        &mut self,
+        sx: u16, sy: u16,
+        ex: u16, ey: u16,
+        data: &[u8],
+    ) -> Result<(), Error<RstE, BlE>> {
+        if data.len() != pixel_count(sx, sy, ex, ey) * self.cache.pixel_format.bytes_per_pixel() {
+            return Err(Error::OutOfBounds);
+        }
+
+        self.start_pixels(sx, sy, ex, ey)?;
+        self.di.send_data(U8(data)).map_err(Error::Interface)
+    }
+
+    ///
+    /// Fills the given rectangle bounds with one color.
+    /// Fails with [`Error::OutOfBounds`] unless the rectangle is inside the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `color` - the Rgb565 color value
+    ///
+    pub fn fill(&mut self, sx: u16, sy: u16, ex: u16, ey: u16, color: u16) -> Result<(), Error<RstE, BlE>> {
+        self.set_pixels(sx, sy, ex, ey, repeat(color).take(pixel_count(sx, sy, ex, ey)))
+    }
+
+    ///
+    /// Writes a whole frame of Rgb565 colors, row by row in the current orientation.
+    /// Fails with [`Error::OutOfBounds`] unless `frame` holds exactly one color per pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - frame buffer of `width * height` colors.
+    ///
+    pub fn flush(&mut self, frame: &[u16]) -> Result<(), Error<RstE, BlE>> {
+        let (width, height) = self.cache.geometry.size(self.cache.madctl);
+        if frame.len() != usize::from(width) * usize::from(height) {
+            return Err(Error::OutOfBounds);
+        }
+
+        self.set_pixels(0, 0, width - 1, height - 1, frame.iter().copied())
+    }
+    
+    ///
+    /// Sets the positive and negative gamma curves.
+    ///
+    pub fn set_gamma(&mut self, gamma: &Gamma) -> Result<(), Error<RstE, BlE>> {
+        self.write_all(sequence::gamma(gamma))
+    }
+
+    ///
+    /// Sets all power control registers.
+    ///
+    pub fn set_power(&mut self, power: &PowerSettings) -> Result<(), Error<RstE, BlE>> {
+        self.write_all(sequence::power(power))
+    }
+
+    ///
+    /// Sets the source and gate driving voltages (PWR1).
+    ///
+    pub fn set_driving_voltages(&mut self, voltages: DrivingVoltages) -> Result<(), Error<RstE, BlE>> {
+        self.write_register(&voltages)
+    }
+
+    ///
+    /// Sets the GVDD/GVCL level (PWR2).
+    ///
+    pub fn set_vrh(&mut self, vrh: Vrh) -> Result<(), Error<RstE, BlE>> {
+        self.write_register(&vrh)
+    }
+
+    ///
+    /// Sets the source and gamma driving currents (PWR3).
+    ///
+    pub fn set_driving_currents(&mut self, currents: DrivingCurrents) -> Result<(), Error<RstE, BlE>> {
+        self.write_register(&currents)
+    }
+
+    ///
+    /// Sets the VCOM voltage (VCMPCTL).
+    ///
+    pub fn set_vcom(&mut self, vcom: Vcom) -> Result<(), Error<RstE, BlE>> {
+        self.write_register(&vcom)
+    }
+
+    ///
+    /// Sets the VCOM offset (VCM Offset).
+    ///
+    pub fn set_vcom_offset(&mut self, offset: VcomOffset) -> Result<(), Error<RstE, BlE>> {
+        self.write_register(&offset)
+    }
+
+    ///
+    /// Sets the display function control: scan directions, non-display
+    /// area scanning and the number of driven lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `display_function` - setting to send, driving at least the panel height.
+    ///
+    pub fn set_display_function(&mut self, display_function: &DisplayFunction) -> Result<(), Error<RstE, BlE>> {
+        let (_, height) = self.cache.geometry.visible_size();
+        if display_function.driving_lines.lines() < height {
+            return Err(Error::InvalidConfig);
+        }
+
+        self.write_register(display_function)
+    }
+
+    ///
+    /// Sets the frame rates of the normal, idle and partial display modes.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame_rates` - settings, e.g. from [`FrameRates::closest`].
+    ///
+    pub fn set_frame_rates(&mut self, frame_rates: FrameRates) -> Result<(), Error<RstE, BlE>> {
+        if !frame_rates.is_valid() {
+            return Err(Error::InvalidConfig);
+        }
+
+        self.write_all(sequence::frame_rates(&frame_rates))?;
+        self.cache.frame_rates = frame_rates;
+        self.cache.unknown.frame_rates = false;
+
+        Ok(())
+    }
+
+    ///
+    /// Sets the display inversion mode (DIC).
+    ///
+    pub fn set_inversion_mode(&mut self, mode: InversionMode) -> Result<(), Error<RstE, BlE>> {
+        self.write_register(&mode)
+    }
+
+    ///
+    /// Defines the vertical scrolling areas and resets the scroll offset to 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - scroll areas, adding up to the GRAM height.
+    ///
+    pub fn set_scroll_region(&mut self, region: ScrollRegion) -> Result<(), Error<RstE, BlE>> {
+        let (_, gram_height) = self.cache.geometry.gram_size();
+        if !region.is_valid(gram_height) {
+            return Err(Error::InvalidConfig);
+        }
+
+        self.write_register(&region)?;
+        self.cache.scroll = ScrollState { region, offset: 0 };
+        self.write_register(&ScrollStartAddress(self.cache.scroll.start_address()))
+    }
+
+    ///
+    /// Sets scroll offset "shifting" the displayed picture
+    /// # Arguments
+    ///
+    /// * `offset` - scroll offset in lines, less than the scrolling area height
+    ///
+    pub fn set_scroll_offset(&mut self, offset: u16) -> Result<(), Error<RstE, BlE>> {
+        if offset >= self.cache.scroll.region.scroll_area {
+            return Err(Error::OutOfBounds);
+        }
+
+        let scroll = ScrollState { offset, ..self.cache.scroll };
+        self.write_register(&ScrollStartAddress(scroll.start_address()))?;
+        self.cache.scroll = scroll;
+
+        Ok(())
+    }
+
+    ///
+    /// Enters partial display mode, driving only the frame memory rows
+    /// `start_row..=end_row`. Rows are frame memory rows, independent of MADCTL.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_row` - first driven row.
+    /// * `end_row` - last driven row, not less than `start_row`.
+    ///
+    pub fn enter_partial_mode(&mut self, start_row: u16, end_row: u16) -> Result<(), Error<RstE, BlE>> {
+        let (_, gram_height) = self.cache.geometry.gram_size();
+        if start_row > end_row || end_row >= gram_height {
+            return Err(Error::OutOfBounds);
+        }
+
+        self.write_register(&PartialArea { start_row, end_row })?;
+        self.write_command(Command::PTLON)?;
+        self.cache.display_mode = DisplayMode::Partial { start_row, end_row };
+
+        Ok(())
+    }
+
+    ///
+    /// Returns to normal display mode, driving the whole panel.
+    ///
+    pub fn exit_partial_mode(&mut self) -> Result<(), Error<RstE, BlE>> {
+        self.write_command(Command::NORON)?;
+        self.cache.display_mode = DisplayMode::Normal;
+
+        Ok(())
+    }
+
+    ///
+    /// Turns display inversion on (INVON) or off (INVOFF).
+    /// IPS panels usually need inversion on to show the frame memory colors.
+    ///
+    pub fn set_inverted(&mut self, inverted: bool) -> Result<(), Error<RstE, BlE>> {
+        self.write_command(if inverted { Command::INVON } else { Command::INVOFF })?;
+        self.cache.inverted = inverted;
+
+        Ok(())
+    }
+
+    ///
+    /// Flashes the display by inverting it for `duration_us`, then restoring
+    /// the previous polarity. The frame memory is left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration_us` - time the display stays inverted.
+    /// * `delay_source` - mutable reference to a delay provider.
+    ///
+    pub fn flash(&mut self, duration_us: u32, delay_source: &mut impl DelayUs<u32>) -> Result<(), Error<RstE, BlE>> {
+        let inverted = self.cache.inverted;
+        self.set_inverted(!inverted)?;
+        delay_source.delay_us(duration_us);
+        self.set_inverted(inverted)
+    }
+
+    ///
+    /// Turns idle mode on (IDMON) or off (IDMOFF).
+    /// In idle mode the display shows 8 colors, using only the most
+    /// significant bit of each color component, and uses less power.
+    ///
+    pub fn set_idle_mode(&mut self, idle: bool) -> Result<(), Error<RstE, BlE>> {
+        self.write_command(if idle { Command::IDMON } else { Command::IDMOFF })?;
+        self.cache.idle_mode = idle;
+
+        Ok(())
+    }
+
+    ///
+    /// Configures the tearing effect output.
+    ///
+    pub fn set_tearing_effect(&mut self, tearing_effect: TearingEffect) -> Result<(), Error<RstE, BlE>> {
+        match tearing_effect {
+            TearingEffect::Off => self.write_command(Command::TEOFF),
+
+            TearingEffect::Vertical => self.write_register(&TearingEffectLine { horizontal: false }),
+
+            TearingEffect::HorizontalVertical => {
+                self.write_register(&TearingEffectLine { horizontal: true })
+            }
+        }
+
+    }
+
+    ///
+    /// Turns the tearing effect output on at display line `scanline` (STE).
+    ///
+    pub fn set_tear_scanline(&mut self, scanline: u16) -> Result<(), Error<RstE, BlE>> {
+        self.write_register(&TearScanline(scanline))
+    }
+
+    ///
+    /// Writes a typed register, such as [`BlankingPorch`] or [`Brightness`],
+    /// enabling Command Table 2 with CSCON when the register needs it.
+    ///
+    /// The cached state is updated as for [`ST7796::send_command`].
+    ///
+    pub fn write_register<I: Instruction>(&mut self, register: &I) -> Result<(), Error<RstE, BlE>> {
+        self.write_all(sequence::register(register))
+    }
+
+    // --- Private Functions --- //
+
+    /// Private method:Sets the address window and starts writing the frame memory (RAMWR).
+    pub(crate) fn start_pixels(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), Error<RstE, BlE>> {
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_command(Command::RAMWR)
+    }
+
+    /// Private method:Sends Rgb565 colors after `start_pixels`, as a slice in the 16 bit format.
+    pub(crate) fn send_pixels(&mut self, colors: &mut [u16]) -> Result<(), Error<RstE, BlE>> {
+        match self.cache.pixel_format {
+            PixelFormat::Rgb565 => self.di.send_data(U16BE(colors)),
+            PixelFormat::Rgb666 | PixelFormat::Rgb888 => self
+                .di
+                .send_data(U8Iter(&mut colors.iter().copied().flat_map(rgb565_to_bytes))),
+        }
+        .map_err(Error::Interface)
+    }
+
+    /// Private method:Sets the address window for the display, shifted by the panel offset.
+    /// Fails with `OutOfBounds` unless the window is inside the visible area.
+    pub(crate) fn set_address_window(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), Error<RstE, BlE>> {
+        let writes = sequence::address_window(&self.cache, sx, sy, ex, ey).ok_or(Error::OutOfBounds)?;
+        self.write_all(writes)
+    }
+}
+
+impl<DI, BL, BlE> ST7796<DI, NoPin, BL, Uninit>
+where
+    DI: WriteOnlyDataCommand,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Creates a new ST7796 driver instance for a display without a reset pin.
+    ///
+    /// # Arguments.
+    ///
+    /// * `di` - Display Interface to communicate with display.
+    /// * `bl` - backlight, such as a [`GpioBacklight`].
+    /// * `size_x` - x axis resolution of the display in pixels, in portrait orientation.
+    /// * `size_y` - y axis resolution of the display in pixels, in portrait orientation.
+    ///
+    pub fn new_without_reset(di: DI, bl: Option<BL>, size_x: u16, size_y: u16) -> Self {
+        Self::new(di, None, bl, size_x, size_y)
+    }
+}
+
+impl<DI, RST, RstE> ST7796<DI, RST, NoPin, Uninit>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+{
+    ///
+    /// Creates a new ST7796 driver instance for a display without a backlight pin.
+    ///
+    /// # Arguments.
+    ///
+    /// * `di` - Display Interface to communicate with display.
+    /// * `rst` - Display hard reset pin.
+    /// * `size_x` - x axis resolution of the display in pixels, in portrait orientation.
+    /// * `size_y` - y axis resolution of the display in pixels, in portrait orientation.
+    ///
+    pub fn new_without_backlight(di: DI, rst: Option<RST>, size_x: u16, size_y: u16) -> Self {
+        Self::new(di, rst, None, size_x, size_y)
+    }
+}
+
+impl<DI> ST7796<DI, NoPin, NoPin, Uninit>
+where
+    DI: WriteOnlyDataCommand,
+{
+    ///
+    /// Creates a new ST7796 driver instance for a display without reset and backlight pins.
+    ///
+    /// # Arguments.
+    ///
+    /// * `di` - Display Interface to communicate with display.
+    /// * `size_x` - x axis resolution of the display in pixels, in portrait orientation.
+    /// * `size_y` - y axis resolution of the display in pixels, in portrait orientation.
+    ///
+    pub fn new_without_pins(di: DI, size_x: u16, size_y: u16) -> Self {
+        Self::new(di, None, None, size_x, size_y)
+    }
+}
+
+/// Returns the number of pixels in the rectangle bounds, 1 if they are reversed.
+pub(crate) fn pixel_count(sx: u16, sy: u16, ex: u16, ey: u16) -> usize {
+    (usize::from(ex.saturating_sub(sx)) + 1) * (usize::from(ey.saturating_sub(sy)) + 1)
+}
+
+/// Expands a Rgb565 value to the 3 bytes sent in the 18 and 24 bit formats.
+fn rgb565_to_bytes(color: u16) -> [u8; 3] {
+    let r = (color >> 11) as u8 & 0x1F;
+    let g = (color >> 5) as u8 & 0x3F;
+    let b = color as u8 & 0x1F;
+
+    [(r << 3) | (r >> 2), (g << 2) | (g >> 4), (b << 3) | (b >> 2)]
+}
+
+/// Reduces a Rgb565 value to the 8 colors shown in idle mode.
+#[cfg(feature = "graphics")]
+pub(crate) fn rgb565_to_idle(color: u16) -> u16 {
+    let r = if color & 0x8000 != 0 { 0xF800 } else { 0 };
+    let g = if color & 0x0400 != 0 { 0x07E0 } else { 0 };
+    let b = if color & 0x0010 != 0 { 0x001F } else { 0 };
+
+    r | g | b
+}
+
+/// Reduces a `0x00RRGGBB` value to the 8 colors shown in idle mode.
+#[cfg(feature = "graphics")]
+pub(crate) fn rgb888_to_idle(color: u32) -> u32 {
+    color.to_be_bytes()[1..]
+        .iter()
+        .fold(0, |idle, &c| idle << 8 | if c & 0x80 != 0 { 0xFF } else { 0 })
+}
+
+/// Reduces a `0x00RRGGBB` value to Rgb565.
+pub(crate) fn rgb888_to_rgb565(color: u32) -> u16 {
+    let [_, r, g, b] = color.to_be_bytes();
+
+    (u16::from(r >> 3) << 11) | (u16::from(g >> 2) << 5) | u16::from(b >> 3)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::mock::{ready_display, MockDelay, MockInterface, MockPin};
+    use std::vec;
+
+    #[test]
+    fn init_resets_configures_and_turns_on() {
+        let mut delay = MockDelay::default();
+        let bl = GpioBacklight::new(MockPin::default());
+        let display = ST7796::new(MockInterface::default(), Some(MockPin::default()), Some(bl), 320, 480)
+            .init(&mut delay)
+            .unwrap_or_else(|_| panic!("init failed"));
+
+        let expected: [(Command, &[u8]); 9] = [
+            (Command::SWRESET, &[]),
+            (Command::SLPOUT, &[]),
+            (Command::VSCRDER, &[0x00, 0x00, 0x01, 0xE0, 0x00, 0x00]),
+            (Command::VSCRSADD, &[0x00, 0x00]),
+            (Command::MADCTL, &[0x00]),
+            (Command::PIXFMT, &[0x55]),
+            (Command::INVON, &[]),
+            (Command::NORON, &[]),
+            (Command::DISPON, &[]),
+        ];
+        let expected: std::vec::Vec<_> = expected.iter().map(|&(command, params)| (command as u8, params.to_vec())).collect();
+        assert_eq!(display.di.commands, expected);
+        assert_eq!(display.power_state(), PowerState { sleeping: false, display_on: true });
+        // hard reset, reset delay, sleep out delay and 3 waits of 10ms
+        assert_eq!(delay.total_us, 30 + 150_000 + 10_000 + 30_000);
+
+        let (_, rst, bl) = display.release();
+        assert_eq!(rst.unwrap().levels, [true, false, true]);
+        assert_eq!(bl.unwrap().release().levels, [true]);
+    }
+
+    #[test]
+    fn hard_reset_and_backlight_set_pins() {
+        let mut delay = MockDelay::default();
+        let bl = GpioBacklight::new(MockPin::default());
+        let mut display = ST7796::new(MockInterface::default(), Some(MockPin::default()), Some(bl), 320, 480);
+        display.hard_reset(&mut delay).unwrap();
+        display.set_backlight(BacklightState::ON, &mut delay).unwrap();
+        display.set_backlight(BacklightState::OFF, &mut delay).unwrap();
+
+        assert_eq!(delay.total_us, 30 + 10 + 10);
+        let (_, rst, bl) = display.release();
+        assert_eq!(rst.unwrap().levels, [true, false, true]);
+        assert_eq!(bl.unwrap().release().levels, [true, false]);
+    }
+
+    #[test]
+    fn init_without_reset_pin_waits_for_sleep_out() {
+        let mut delay = MockDelay::default();
+        ST7796::new_without_pins(MockInterface::default(), 320, 480)
+            .init(&mut delay)
+            .unwrap_or_else(|_| panic!("init failed"));
+
+        assert_eq!(delay.total_us, 120_000 + 150_000 + 10_000 + 30_000);
+    }
+
+    #[test]
+    fn init_waits_at_least_5ms_after_sleep_out() {
+        let mut delay = MockDelay::default();
+        let config = InitConfig::default().with_sleep_out_delay_us(0);
+        let display = ST7796::new_without_pins(MockInterface::default(), 320, 480)
+            .init_with(&config, &mut delay)
+            .unwrap_or_else(|_| panic!("init failed"));
+
+        assert_eq!(delay.total_us, 120_000 + 150_000 + 5_000 + 30_000);
+        // SLPIN still waits for the rest of the 120ms, less the waits after SLPOUT
+        let mut delay = MockDelay::default();
+        display.sleep(&mut delay).unwrap_or_else(|_| panic!("sleep failed"));
+        assert_eq!(delay.total_us, 85_000 + 5_000);
+    }
+
+    #[test]
+    fn sleep_after_counts_elapsed_time() {
+        let mut delay = MockDelay::default();
+        // init waited 40ms since SLPOUT
+        let display = ready_display().sleep_after(70_000, &mut delay).unwrap_or_else(|_| panic!("sleep failed"));
+        assert_eq!(delay.total_us, 10_000 + 5_000);
+
+        let mut delay = MockDelay::default();
+        let display = display.wake(&mut delay).unwrap_or_else(|_| panic!("wake failed"));
+        let display = display.sleep_after(1_000_000, &mut delay).unwrap_or_else(|_| panic!("sleep failed"));
+        assert_eq!(delay.total_us, 5_000 + 5_000);
+        assert_eq!(display.di.command_bytes(), [Command::SLPIN, Command::SLPOUT, Command::SLPIN].map(|command| command as u8));
+    }
+
+    #[test]
+    fn init_rejects_invalid_geometry() {
+        let geometry = PanelGeometry::new(320, 480).with_offset(0, 1);
+        let result = ST7796::<_, NoPin, NoPin, _>::with_geometry(MockInterface::default(), None, None, geometry)
+            .init(&mut MockDelay::default());
+
+        assert!(matches!(result, Err((_, Error::InvalidConfig))));
+    }
+
+    #[test]
+    fn init_rejects_state_commands_and_hands_back_the_driver() {
+        let config = InitConfig::default().with_commands(&[(Command::SLPIN, &[])]);
+        let display = ST7796::new_without_pins(MockInterface::default(), 320, 480);
+        let (display, error) = match display.init_with(&config, &mut MockDelay::default()) {
+            Ok(_) => panic!("init accepted SLPIN"),
+            Err(failure) => failure,
+        };
+
+        assert!(matches!(error, Error::InvalidConfig));
+        assert!(display.di.commands.is_empty());
+        let display = display.init(&mut MockDelay::default()).unwrap_or_else(|_| panic!("init failed"));
+        assert_eq!(display.power_state(), PowerState { sleeping: false, display_on: true });
+    }
+
+    #[test]
+    fn write_pixels_sends_one_big_endian_slice() {
+        let mut display = ready_display();
+        let mut colors: std::vec::Vec<u16> = (0..300).map(|i| 0x1200 | i).collect();
+        let expected: std::vec::Vec<u8> = colors.iter().flat_map(|color| color.to_be_bytes()).collect();
+        display.write_pixels(0, 0, 29, 9, &mut colors).unwrap();
+
+        assert_eq!(display.di.params(Command::RAMWR), Some(expected.as_slice()));
+        // CASET, RASET and the colors
+        assert_eq!(display.di.data_writes, 3);
+    }
+
+    #[test]
+    fn pixel_buffers_must_match_the_window() {
+        let mut display = ready_display();
+
+        assert!(matches!(display.write_pixels(0, 0, 1, 1, &mut [0; 3]), Err(Error::OutOfBounds)));
+        assert!(matches!(display.write_pixels_be(0, 0, 1, 1, &[0; 7]), Err(Error::OutOfBounds)));
+        assert!(display.di.commands.is_empty());
+
+        display.write_pixels_be(0, 0, 1, 1, &[0; 8]).unwrap();
+        display.set_pixel_format(PixelFormat::Rgb666).unwrap();
+        assert!(matches!(display.write_pixels_be(0, 0, 1, 1, &[0; 8]), Err(Error::OutOfBounds)));
+        display.write_pixels_be(0, 0, 1, 1, &[0; 12]).unwrap();
+    }
+
+    #[test]
+    fn command2_registers_are_enabled_with_cscon() {
+        let mut display = ready_display();
+        display.set_gamma(&Gamma::SITRONIX_REFERENCE).unwrap();
+
+        let gamma = Gamma::SITRONIX_REFERENCE;
+        let cscon = Command::CSCON as u8;
+        assert_eq!(
+            display.di.commands,
+            [
+                (cscon, vec![0xC3]),
+                (cscon, vec![0x96]),
+                (Command::GMCTRP1 as u8, gamma.positive.to_bytes().to_vec()),
+                (Command::GMCTRN1 as u8, gamma.negative.to_bytes().to_vec()),
+                (cscon, vec![0x3C]),
+                (cscon, vec![0x69]),
+            ]
+        );
+    }
+
+    #[test]
+    fn command1_registers_are_sent_without_cscon() {
+        let mut display = ready_display();
+        display.set_orientation(Rotation::Deg90).unwrap();
+
+        assert_eq!(display.di.command_bytes(), [Command::MADCTL as u8]);
+        assert_eq!(display.di.params(Command::MADCTL), Some(&[Madctl::from(Rotation::Deg90).bits()][..]));
+    }
 }
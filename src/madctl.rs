@@ -0,0 +1,231 @@
+//! Memory Data Access Control (MADCTL, 36h) register.
+
+use crate::instruction::{Command, Instruction};
+use crate::Orientation;
+
+/// D7: row address order.
+const MY: u8 = 0b1000_0000;
+/// D6: column address order.
+const MX: u8 = 0b0100_0000;
+/// D5: row/column exchange.
+const MV: u8 = 0b0010_0000;
+/// D4: vertical refresh order.
+const ML: u8 = 0b0001_0000;
+/// D3: RGB-BGR order.
+const BGR: u8 = 0b0000_1000;
+/// D2: horizontal refresh order.
+const MH: u8 = 0b0000_0100;
+
+/// Address order bits of the unmirrored rotations.
+const DEG90: u8 = MX | MV;
+const DEG180: u8 = MY | MX;
+const DEG270: u8 = MY | MV;
+
+///
+/// Rotation of the picture, relative to portrait.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Rotation {
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+///
+/// MADCTL parameter, with every bit exposed.
+/// (For More refer on Datasheet MADCTL pg.no:183)
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Madctl(u8);
+
+impl Madctl {
+    ///
+    /// All bits cleared: portrait, RGB, top to bottom and left to right refresh.
+    ///
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    ///
+    /// Creates a value from the raw parameter byte. D1 and D0 are unused and ignored.
+    ///
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits & 0b1111_1100)
+    }
+
+    ///
+    /// Returns the raw parameter byte.
+    ///
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    ///
+    /// Creates the address order bits for `rotation`, optionally mirrored
+    /// horizontally. All other bits are cleared.
+    ///
+    pub const fn from_rotation(rotation: Rotation, mirrored: bool) -> Self {
+        let bits = match rotation {
+            Rotation::Deg0 => 0,
+            Rotation::Deg90 => DEG90,
+            Rotation::Deg180 => DEG180,
+            Rotation::Deg270 => DEG270,
+        };
+        let madctl = Self(bits);
+
+        if !mirrored {
+            madctl
+        } else if madctl.swap_xy() {
+            Self(bits ^ MY)
+        } else {
+            Self(bits ^ MX)
+        }
+    }
+
+    ///
+    /// Returns the rotation and mirroring encoded by the address order bits.
+    ///
+    pub const fn rotation(self) -> (Rotation, bool) {
+        match self.0 & (MY | MX | MV) {
+            0 => (Rotation::Deg0, false),
+            MX => (Rotation::Deg0, true),
+            DEG90 => (Rotation::Deg90, false),
+            DEG180 => (Rotation::Deg180, false),
+            MY => (Rotation::Deg180, true),
+            DEG270 => (Rotation::Deg270, false),
+            MV => (Rotation::Deg270, true),
+            _ => (Rotation::Deg90, true),
+        }
+    }
+
+    /// Row address order (MY).
+    pub const fn mirror_y(self) -> bool {
+        self.0 & MY != 0
+    }
+
+    /// Column address order (MX).
+    pub const fn mirror_x(self) -> bool {
+        self.0 & MX != 0
+    }
+
+    /// Row/column exchange (MV).
+    pub const fn swap_xy(self) -> bool {
+        self.0 & MV != 0
+    }
+
+    /// Vertical refresh order (ML), `true` is bottom to top.
+    pub const fn vertical_refresh_bottom_to_top(self) -> bool {
+        self.0 & ML != 0
+    }
+
+    /// Color filter order, `true` is BGR.
+    pub const fn bgr(self) -> bool {
+        self.0 & BGR != 0
+    }
+
+    /// Horizontal refresh order (MH), `true` is right to left.
+    pub const fn horizontal_refresh_right_to_left(self) -> bool {
+        self.0 & MH != 0
+    }
+
+    /// Sets the row address order (MY).
+    pub const fn with_mirror_y(self, value: bool) -> Self {
+        self.with_bit(MY, value)
+    }
+
+    /// Sets the column address order (MX).
+    pub const fn with_mirror_x(self, value: bool) -> Self {
+        self.with_bit(MX, value)
+    }
+
+    /// Sets the row/column exchange (MV).
+    pub const fn with_swap_xy(self, value: bool) -> Self {
+        self.with_bit(MV, value)
+    }
+
+    /// Sets the vertical refresh order (ML), `true` is bottom to top.
+    pub const fn with_vertical_refresh_bottom_to_top(self, value: bool) -> Self {
+        self.with_bit(ML, value)
+    }
+
+    /// Sets the color filter order, `true` is BGR.
+    pub const fn with_bgr(self, value: bool) -> Self {
+        self.with_bit(BGR, value)
+    }
+
+    /// Sets the horizontal refresh order (MH), `true` is right to left.
+    pub const fn with_horizontal_refresh_right_to_left(self, value: bool) -> Self {
+        self.with_bit(MH, value)
+    }
+
+    const fn with_bit(self, mask: u8, value: bool) -> Self {
+        if value {
+            Self(self.0 | mask)
+        } else {
+            Self(self.0 & !mask)
+        }
+    }
+}
+
+impl Instruction for Madctl {
+    const COMMAND: Command = Command::MADCTL;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [self.0]
+    }
+}
+
+impl From<Orientation> for Madctl {
+    fn from(orientation: Orientation) -> Self {
+        Self(orientation as u8)
+    }
+}
+
+impl From<Rotation> for Madctl {
+    fn from(rotation: Rotation) -> Self {
+        Self::from_rotation(rotation, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROTATIONS: [Rotation; 4] = [Rotation::Deg0, Rotation::Deg90, Rotation::Deg180, Rotation::Deg270];
+
+    #[test]
+    fn rotation_round_trips() {
+        for rotation in ROTATIONS {
+            for mirrored in [false, true] {
+                let madctl = Madctl::from_rotation(rotation, mirrored);
+                assert_eq!(madctl.rotation(), (rotation, mirrored));
+                // other bits do not change the rotation
+                let other_bits = madctl.with_bgr(true).with_vertical_refresh_bottom_to_top(true);
+                assert_eq!(other_bits.rotation(), (rotation, mirrored));
+            }
+        }
+    }
+
+    #[test]
+    fn orientations_match_rotations() {
+        assert_eq!(Madctl::from(Orientation::Portrait), Madctl::from(Rotation::Deg0));
+        assert_eq!(Madctl::from(Orientation::Landscape), Madctl::from(Rotation::Deg90));
+        assert_eq!(Madctl::from(Orientation::PortraitSwapped), Madctl::from(Rotation::Deg180));
+        assert_eq!(Madctl::from(Orientation::LandscapeSwapped), Madctl::from(Rotation::Deg270));
+    }
+
+    #[test]
+    fn bits_round_trip_without_unused_bits() {
+        let madctl = Madctl::new()
+            .with_mirror_y(true)
+            .with_swap_xy(true)
+            .with_bgr(true)
+            .with_horizontal_refresh_right_to_left(true);
+        assert_eq!(madctl.bits(), 0b1010_1100);
+        assert_eq!(Madctl::from_bits(0xFF).bits(), 0b1111_1100);
+        assert_eq!(madctl.with_swap_xy(false).bits(), 0b1000_1100);
+        assert_eq!(madctl.to_bytes(), [0b1010_1100]);
+    }
+}
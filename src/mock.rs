@@ -0,0 +1,240 @@
+//! Recording display interface, pin and delay used by the unit tests.
+
+extern crate std;
+
+use crate::instruction::Command;
+use crate::{NoPin, ReadWriteDataCommand, ST7796};
+use core::convert::Infallible;
+use display_interface::DataFormat::{self, U16BEIter, U16LEIter, U8Iter, U16, U16BE, U16LE, U8};
+use display_interface::{DisplayError, WriteOnlyDataCommand};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::digital::v2::OutputPin;
+use std::vec::Vec;
+
+///
+/// Display interface recording every command byte with the data sent after it.
+/// Reads are recorded as commands too, and answered from `read_data`.
+///
+#[derive(Debug, Default)]
+pub struct MockInterface {
+    pub commands: Vec<(u8, Vec<u8>)>,
+    /// Number of `send_data` calls.
+    pub data_writes: usize,
+    pub read_data: Vec<u8>,
+}
+
+impl MockInterface {
+    /// Returns the command bytes, in the order they were sent.
+    pub fn command_bytes(&self) -> Vec<u8> {
+        self.commands.iter().map(|(command, _)| *command).collect()
+    }
+
+    /// Returns the parameters sent with the last `command`.
+    pub fn params(&self, command: Command) -> Option<&[u8]> {
+        self.commands
+            .iter()
+            .rev()
+            .find(|(sent, _)| *sent == command as u8)
+            .map(|(_, params)| params.as_slice())
+    }
+}
+
+impl WriteOnlyDataCommand for MockInterface {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        for command in to_bytes(cmd) {
+            self.commands.push((command, Vec::new()));
+        }
+
+        Ok(())
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        let (_, params) = self.commands.last_mut().ok_or(DisplayError::BusWriteError)?;
+        params.extend(to_bytes(buf));
+        self.data_writes += 1;
+
+        Ok(())
+    }
+}
+
+impl ReadWriteDataCommand for MockInterface {
+    fn read_command(&mut self, command: u8, buf: &mut [u8]) -> Result<(), DisplayError> {
+        if buf.len() > self.read_data.len() {
+            return Err(DisplayError::BusWriteError);
+        }
+
+        self.commands.push((command, Vec::new()));
+        let len = buf.len();
+        buf.copy_from_slice(&self.read_data[..len]);
+        self.read_data.drain(..len);
+
+        Ok(())
+    }
+}
+
+/// Returns the bytes `data` puts on the bus.
+fn to_bytes(data: DataFormat<'_>) -> Vec<u8> {
+    match data {
+        U8(data) => data.to_vec(),
+        U16(data) => data.iter().flat_map(|value| value.to_ne_bytes()).collect(),
+        U16BE(data) => data.iter().flat_map(|value| value.to_be_bytes()).collect(),
+        U16LE(data) => data.iter().flat_map(|value| value.to_le_bytes()).collect(),
+        U8Iter(iter) => iter.collect(),
+        U16BEIter(iter) => iter.flat_map(u16::to_be_bytes).collect(),
+        U16LEIter(iter) => iter.flat_map(u16::to_le_bytes).collect(),
+        _ => unimplemented!("data format not used by the driver"),
+    }
+}
+
+#[cfg(feature = "async")]
+impl display_interface_async::AsyncWriteOnlyDataCommand for MockInterface {
+    async fn send_commands(
+        &mut self,
+        cmd: display_interface_async::DataFormat<'_>,
+    ) -> Result<(), display_interface_async::DisplayError> {
+        for command in async_to_bytes(cmd) {
+            self.commands.push((command, Vec::new()));
+        }
+
+        Ok(())
+    }
+
+    async fn send_data(
+        &mut self,
+        buf: display_interface_async::DataFormat<'_>,
+    ) -> Result<(), display_interface_async::DisplayError> {
+        let (_, params) = self
+            .commands
+            .last_mut()
+            .ok_or(display_interface_async::DisplayError::BusWriteError)?;
+        params.extend(async_to_bytes(buf));
+        self.data_writes += 1;
+
+        Ok(())
+    }
+}
+
+/// Returns the bytes `data` of the async interface puts on the bus.
+#[cfg(feature = "async")]
+fn async_to_bytes(data: display_interface_async::DataFormat<'_>) -> Vec<u8> {
+    use display_interface_async::DataFormat as F;
+
+    match data {
+        F::U8(data) => to_bytes(U8(data)),
+        F::U16(data) => to_bytes(U16(data)),
+        F::U16BE(data) => to_bytes(U16BE(data)),
+        F::U16LE(data) => to_bytes(U16LE(data)),
+        F::U8Iter(iter) => to_bytes(U8Iter(iter)),
+        F::U16BEIter(iter) => to_bytes(U16BEIter(iter)),
+        F::U16LEIter(iter) => to_bytes(U16LEIter(iter)),
+        _ => unimplemented!("data format not used by the driver"),
+    }
+}
+
+///
+/// Output pin recording every level it was set to, `true` is high.
+///
+#[derive(Debug, Default)]
+pub struct MockPin {
+    pub levels: Vec<bool>,
+}
+
+impl OutputPin for MockPin {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.levels.push(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.levels.push(true);
+        Ok(())
+    }
+}
+
+///
+/// Delay adding up the time waited instead of waiting.
+///
+#[derive(Debug, Default)]
+pub struct MockDelay {
+    pub total_us: u64,
+}
+
+impl DelayUs<u32> for MockDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total_us += u64::from(us);
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl embedded_hal_1::digital::ErrorType for MockPin {
+    type Error = Infallible;
+}
+
+#[cfg(feature = "eh1")]
+impl embedded_hal_1::digital::OutputPin for MockPin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        OutputPin::set_low(self)
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        OutputPin::set_high(self)
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl embedded_hal_1::delay::DelayNs for MockDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.total_us += u64::from(ns / 1_000);
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        DelayUs::delay_us(self, us);
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for MockDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.total_us += u64::from(ns / 1_000);
+    }
+
+    async fn delay_us(&mut self, us: u32) {
+        DelayUs::delay_us(self, us);
+    }
+}
+
+///
+/// Polls `future` to completion; the mocks never return `Pending`.
+///
+#[cfg(feature = "async")]
+pub fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn noop_raw_waker() -> RawWaker {
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(|_| noop_raw_waker(), |_| {}, |_| {}, |_| {});
+
+    // SAFETY: the vtable functions do nothing with the null data pointer.
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = core::pin::pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+///
+/// Returns an initialized 320x480 driver without pins, with nothing recorded yet.
+///
+pub fn ready_display() -> ST7796<MockInterface, NoPin, NoPin> {
+    let mut display = ST7796::new_without_pins(MockInterface::default(), 320, 480)
+        .init(&mut MockDelay::default())
+        .unwrap_or_else(|_| panic!("init failed"));
+    display.di = MockInterface::default();
+    display
+}
@@ -0,0 +1,293 @@
+//! Power control registers (PWR1 C0h, PWR2 C1h, PWR3 C2h, VCMPCTL C5h, VCM Offset C6h).
+//!
+//! Values are checked against the ranges of the datasheet when created;
+//! constructors return `None` for reserved or out of range settings.
+
+use crate::instruction::{Command, Instruction};
+
+///
+/// PWR1: source (AVDD/AVCL) and gate (VGH/VGL) driving voltages.
+///
+/// Each field is the register code of the datasheet voltage table.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DrivingVoltages {
+    avdd: u8,
+    avcl: u8,
+    vgh: u8,
+    vgl: u8,
+}
+
+impl DrivingVoltages {
+    ///
+    /// Creates the PWR1 setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `avdd` - `AVDDS[1:0]`, `0..=3`.
+    /// * `avcl` - `AVCLS[1:0]`, `0..=3`.
+    /// * `vgh` - `VGHS[2:0]`, `0..=7`.
+    /// * `vgl` - `VGLS[2:0]`, `0..=7`.
+    ///
+    pub const fn new(avdd: u8, avcl: u8, vgh: u8, vgl: u8) -> Option<Self> {
+        if avdd > 3 || avcl > 3 || vgh > 7 || vgl > 7 {
+            return None;
+        }
+
+        Some(Self { avdd, avcl, vgh, vgl })
+    }
+}
+
+impl Instruction for DrivingVoltages {
+    const COMMAND: Command = Command::PWCTR1;
+    type Bytes = [u8; 2];
+
+    fn to_bytes(&self) -> [u8; 2] {
+        [self.avdd << 6 | self.avcl << 4, self.vgh << 4 | self.vgl]
+    }
+}
+
+impl Default for DrivingVoltages {
+    /// Register defaults: 80h, 25h.
+    fn default() -> Self {
+        Self {
+            avdd: 0b10,
+            avcl: 0b00,
+            vgh: 0b010,
+            vgl: 0b101,
+        }
+    }
+}
+
+///
+/// PWR2: VRH, the GVDD (VAP) and GVCL (VAN) gamma reference level.
+///
+/// VAP = 3.55V + 50mV * VRH, VAN = -VAP, both relative to VCOM + VCOM offset.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Vrh(u8);
+
+impl Vrh {
+    /// Largest defined VRH code (5.5V).
+    pub const MAX: u8 = 0x27;
+
+    ///
+    /// Creates the setting from the VRH code, `0..=0x27`.
+    ///
+    pub const fn new(vrh: u8) -> Option<Self> {
+        if vrh > Self::MAX {
+            return None;
+        }
+
+        Some(Self(vrh))
+    }
+
+    ///
+    /// Creates the setting from VAP in millivolts, `3550..=5500` in 50mV steps.
+    ///
+    pub const fn from_millivolts(vap: u16) -> Option<Self> {
+        if vap < 3550 || vap > 5500 || (vap - 3550) % 50 != 0 {
+            return None;
+        }
+
+        Self::new(((vap - 3550) / 50) as u8)
+    }
+
+    /// Returns the VRH code.
+    pub const fn code(self) -> u8 {
+        self.0
+    }
+
+    /// Returns VAP in millivolts.
+    pub const fn millivolts(self) -> u16 {
+        3550 + self.0 as u16 * 50
+    }
+}
+
+impl Instruction for Vrh {
+    const COMMAND: Command = Command::PWCTR2;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [self.0]
+    }
+}
+
+impl Default for Vrh {
+    /// Register default: 13h (4.5V).
+    fn default() -> Self {
+        Self(0x13)
+    }
+}
+
+///
+/// Driving current level of PWR3.
+///
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DrivingCurrent {
+    NoOperation = 0b00,
+    Low = 0b01,
+    Medium = 0b10,
+    High = 0b11,
+}
+
+///
+/// PWR3: source (SOP) and gamma (GOP) driving current levels.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DrivingCurrents {
+    pub source: DrivingCurrent,
+    pub gamma: DrivingCurrent,
+}
+
+impl Instruction for DrivingCurrents {
+    const COMMAND: Command = Command::PWCTR3;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [(self.source as u8) << 2 | self.gamma as u8]
+    }
+}
+
+impl Default for DrivingCurrents {
+    /// Medium source and gamma driving currents.
+    fn default() -> Self {
+        Self {
+            source: DrivingCurrent::Medium,
+            gamma: DrivingCurrent::Medium,
+        }
+    }
+}
+
+///
+/// VCMPCTL: VCOM voltage, VCOM = 0.3V + 25mV * VCMP.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Vcom(u8);
+
+impl Vcom {
+    ///
+    /// Creates the setting from the VCMP code, `0..=0x3F`.
+    ///
+    pub const fn new(vcmp: u8) -> Option<Self> {
+        if vcmp > 0x3F {
+            return None;
+        }
+
+        Some(Self(vcmp))
+    }
+
+    ///
+    /// Creates the setting from VCOM in millivolts, `300..=1875` in 25mV steps.
+    ///
+    pub const fn from_millivolts(vcom: u16) -> Option<Self> {
+        if vcom < 300 || vcom > 1875 || (vcom - 300) % 25 != 0 {
+            return None;
+        }
+
+        Self::new(((vcom - 300) / 25) as u8)
+    }
+
+    /// Returns the VCMP code.
+    pub const fn code(self) -> u8 {
+        self.0
+    }
+
+    /// Returns VCOM in millivolts.
+    pub const fn millivolts(self) -> u16 {
+        300 + self.0 as u16 * 25
+    }
+}
+
+impl Instruction for Vcom {
+    const COMMAND: Command = Command::VMCTR1;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        [self.0]
+    }
+}
+
+impl Default for Vcom {
+    /// Register default: 1Ch (1.0V).
+    fn default() -> Self {
+        Self(0x1C)
+    }
+}
+
+///
+/// VCM Offset: VCOM offset in VCMP steps, or the value stored in NVM.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct VcomOffset(Option<i8>);
+
+impl VcomOffset {
+    /// Use the offset programmed in NVM (VMFSEL = 0).
+    pub const NVM: VcomOffset = VcomOffset(None);
+
+    ///
+    /// Creates a register offset, `-32..=31` steps (VMFSEL = 1).
+    ///
+    pub const fn steps(steps: i8) -> Option<Self> {
+        if steps < -32 || steps > 31 {
+            return None;
+        }
+
+        Some(Self(Some(steps)))
+    }
+}
+
+impl Instruction for VcomOffset {
+    const COMMAND: Command = Command::VCMOFFSET;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(&self) -> [u8; 1] {
+        match self.0 {
+            None => [0],
+            Some(steps) => [0b1000_0000 | (steps as u8 & 0x3F)],
+        }
+    }
+}
+
+///
+/// All power control registers, sent together by `ST7796::set_power`.
+/// The default is the register defaults of the datasheet, with medium
+/// driving currents and the NVM VCOM offset.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PowerSettings {
+    pub driving_voltages: DrivingVoltages,
+    pub vrh: Vrh,
+    pub driving_currents: DrivingCurrents,
+    pub vcom: Vcom,
+    pub vcom_offset: VcomOffset,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn millivolts_round_trip() {
+        for code in 0..=Vrh::MAX {
+            let vrh = Vrh::new(code).unwrap();
+            assert_eq!(Vrh::from_millivolts(vrh.millivolts()), Some(vrh));
+        }
+        for code in 0..=0x3F {
+            let vcom = Vcom::new(code).unwrap();
+            assert_eq!(Vcom::from_millivolts(vcom.millivolts()), Some(vcom));
+        }
+    }
+
+    #[test]
+    fn millivolts_outside_the_range_are_rejected() {
+        assert_eq!(Vrh::from_millivolts(3500), None);
+        assert_eq!(Vrh::from_millivolts(3575), None);
+        assert_eq!(Vrh::from_millivolts(5550), None);
+        // (16350 - 3550) / 50 is 256, which would wrap to code 0
+        assert_eq!(Vrh::from_millivolts(16350), None);
+        assert_eq!(Vcom::from_millivolts(275), None);
+        assert_eq!(Vcom::from_millivolts(1900), None);
+    }
+}
@@ -0,0 +1,442 @@
+//! Raw commands, and keeping the cached display state in step with them.
+
+use crate::instruction::{Command, PartialArea};
+use crate::sequence::Step;
+use crate::{
+    Backlight, ClockDivider, DisplayMode, Error, FrameRate, FrameRates, Madctl, Orientation, PanelGeometry, PixelFormat,
+    PowerState, Ready, ScrollRegion, ScrollState, SLEEP_OUT_TO_SLEEP_IN_US, ST7796,
+};
+use display_interface::WriteOnlyDataCommand;
+use embedded_hal::digital::v2::OutputPin;
+
+///
+/// Cached driver state that raw commands left undefined.
+///
+/// A flag is set when a command that changes the state was sent with
+/// parameters the driver could not decode, and cleared when the state is
+/// set again.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct UnknownState {
+    /// MADCTL, see `ST7796::orientation`.
+    pub orientation: bool,
+    /// COLMOD, see `ST7796::pixel_format`.
+    pub pixel_format: bool,
+    /// Scroll areas and offset, see `ST7796::scroll_state`.
+    pub scroll: bool,
+    /// Partial area, see `ST7796::display_mode`.
+    pub display_mode: bool,
+    /// Frame rates, see `ST7796::frame_rates`.
+    pub frame_rates: bool,
+}
+
+impl UnknownState {
+    /// Returns true if any state is unknown.
+    pub fn any(&self) -> bool {
+        self.orientation || self.pixel_format || self.scroll || self.display_mode || self.frame_rates
+    }
+}
+
+impl<DI, RST, BL, RstE, BlE> ST7796<DI, RST, BL, Ready>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Sends a command with its parameters as they are, for vendor
+    /// sequences the driver has no API for.
+    ///
+    /// The cached state (orientation, pixel format, scrolling, display and
+    /// power modes, frame rates) is updated from the parameters; state the
+    /// parameters do not define is reported by [`ST7796::unknown_state`].
+    /// Command Table 2 registers need CSCON to be sent first.
+    ///
+    /// Commands that leave the driver state (SWRESET, SLPIN, SLPOUT and
+    /// DISPOFF) fail with [`Error::InvalidConfig`] without being sent: use
+    /// [`ST7796::sleep`], [`ST7796::display_off`] or `init_with` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - a [`Command`] or command byte.
+    /// * `params` - parameter bytes, may be empty.
+    ///
+    pub fn send_command(&mut self, command: impl Into<u8>, params: &[u8]) -> Result<(), Error<RstE, BlE>> {
+        let command = command.into();
+        let known = Command::try_from(command).ok();
+        if known.is_some_and(Command::changes_driver_state) {
+            return Err(Error::InvalidConfig);
+        }
+
+        self.write_raw(command, params)?;
+        if let Some(command) = known {
+            self.cache.sync(command, params);
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, RST, BL, S, RstE, BlE> ST7796<DI, RST, BL, S>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Returns the cached state raw commands left undefined.
+    ///
+    pub fn unknown_state(&self) -> UnknownState {
+        self.cache.unknown
+    }
+}
+
+///
+/// Display state cached by the drivers, kept in step with every command sent.
+///
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct CachedState {
+    /// Visible size and GRAM offset.
+    pub geometry: PanelGeometry,
+    /// Current MADCTL setting.
+    pub madctl: Madctl,
+    /// Current COLMOD setting.
+    pub pixel_format: PixelFormat,
+    /// Normal or partial display mode.
+    pub display_mode: DisplayMode,
+    /// Idle (8 color) mode on.
+    pub idle_mode: bool,
+    /// Display inversion on.
+    pub inverted: bool,
+    /// Vertical scroll areas and offset.
+    pub scroll: ScrollState,
+    /// FRMCTR1-3 settings.
+    pub frame_rates: FrameRates,
+    /// Sleep and display on/off state.
+    pub power_state: PowerState,
+    /// Time still to wait after SLPOUT before SLPIN may be sent.
+    pub sleep_in_wait_us: u32,
+    /// PTLAR setting, used by PTLON.
+    pub partial_area: PartialArea,
+    /// State left undefined by raw commands.
+    pub unknown: UnknownState,
+    /// WRDISBV setting.
+    pub brightness: u8,
+}
+
+impl CachedState {
+    /// Returns the state of a driver that has not talked to the display yet.
+    pub(crate) fn new(geometry: PanelGeometry) -> Self {
+        Self {
+            geometry,
+            madctl: Orientation::default().into(),
+            pixel_format: PixelFormat::Rgb565,
+            display_mode: DisplayMode::Normal,
+            idle_mode: false,
+            inverted: false,
+            scroll: ScrollState::default(),
+            frame_rates: FrameRates::default(),
+            power_state: PowerState::default(),
+            sleep_in_wait_us: 0,
+            partial_area: PartialArea { start_row: 0, end_row: 0x01DF },
+            unknown: UnknownState::default(),
+            brightness: 0,
+        }
+    }
+
+    /// Updates the state after a step of a command sequence.
+    pub(crate) fn apply(&mut self, step: &Step<'_>) {
+        match step {
+            Step::Write(command, params) => self.sync(*command, params.as_slice()),
+            Step::Wait(us) => self.sleep_in_wait_us = self.sleep_in_wait_us.saturating_sub(*us),
+        }
+    }
+
+    /// Resets the cached state to the reset defaults of the datasheet.
+    pub(crate) fn reset(&mut self, hardware: bool) {
+        if hardware {
+            self.madctl = Madctl::new();
+            self.pixel_format = PixelFormat::Rgb666;
+            self.unknown.orientation = false;
+            self.unknown.pixel_format = false;
+        }
+        self.power_state = PowerState::default();
+        self.sleep_in_wait_us = 0;
+        self.idle_mode = false;
+        self.inverted = false;
+        self.display_mode = DisplayMode::Normal;
+        self.partial_area = PartialArea { start_row: 0, end_row: 0x01DF };
+        self.scroll = ScrollState {
+            region: ScrollRegion::full(0x01E0),
+            offset: 0,
+        };
+        self.unknown.scroll = false;
+        self.unknown.display_mode = false;
+        self.brightness = 0;
+    }
+
+    /// Updates the cached state after `command` was sent with `params`.
+    pub(crate) fn sync(&mut self, command: Command, params: &[u8]) {
+        match command {
+            Command::SWRESET => self.reset(false),
+            Command::SLPIN => self.power_state.sleeping = true,
+            Command::SLPOUT => {
+                self.power_state.sleeping = false;
+                self.sleep_in_wait_us = SLEEP_OUT_TO_SLEEP_IN_US;
+            }
+            Command::DISPOFF => self.power_state.display_on = false,
+            Command::DISPON => self.power_state.display_on = true,
+            Command::INVOFF => self.inverted = false,
+            Command::INVON => self.inverted = true,
+            Command::IDMOFF => self.idle_mode = false,
+            Command::IDMON => self.idle_mode = true,
+            Command::NORON => self.display_mode = DisplayMode::Normal,
+            Command::PTLON => {
+                let PartialArea { start_row, end_row } = self.partial_area;
+                self.display_mode = DisplayMode::Partial { start_row, end_row };
+            }
+            Command::PTLAR => self.sync_partial_area(params),
+            Command::MADCTL => match params.first() {
+                Some(&bits) => {
+                    self.madctl = Madctl::from_bits(bits);
+                    self.unknown.orientation = false;
+                }
+                None => self.unknown.orientation = true,
+            },
+            Command::PIXFMT => match params.first().and_then(|&bits| PixelFormat::from_bits(bits)) {
+                Some(pixel_format) => {
+                    self.pixel_format = pixel_format;
+                    self.unknown.pixel_format = false;
+                }
+                None => self.unknown.pixel_format = true,
+            },
+            Command::VSCRDER => self.sync_scroll_region(params),
+            Command::VSCRSADD => match params {
+                [high, low, ..] => self.sync_scroll_start(u16::from_be_bytes([*high, *low])),
+                _ => self.unknown.scroll = true,
+            },
+            Command::FRMCTR1 | Command::FRMCTR2 | Command::FRMCTR3 => self.sync_frame_rate(command, params),
+            Command::WRDISBV => {
+                if let Some(&brightness) = params.first() {
+                    self.brightness = brightness;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Updates the partial area from PTLAR parameters.
+    fn sync_partial_area(&mut self, params: &[u8]) {
+        let (_, gram_height) = self.geometry.gram_size();
+        let area = match params {
+            [sh, sl, eh, el, ..] => PartialArea {
+                start_row: u16::from_be_bytes([*sh, *sl]),
+                end_row: u16::from_be_bytes([*eh, *el]),
+            },
+            _ => {
+                self.unknown.display_mode = true;
+                return;
+            }
+        };
+        if area.start_row > area.end_row || area.end_row >= gram_height {
+            self.unknown.display_mode = true;
+            return;
+        }
+
+        self.partial_area = area;
+        self.unknown.display_mode = false;
+        if let DisplayMode::Partial { .. } = self.display_mode {
+            self.display_mode = DisplayMode::Partial {
+                start_row: area.start_row,
+                end_row: area.end_row,
+            };
+        }
+    }
+
+    /// Updates the scroll areas from VSCRDEF parameters, keeping the start address.
+    fn sync_scroll_region(&mut self, params: &[u8]) {
+        let (_, gram_height) = self.geometry.gram_size();
+        let region = match params {
+            [th, tl, vh, vl, bh, bl, ..] => ScrollRegion::new(
+                u16::from_be_bytes([*th, *tl]),
+                u16::from_be_bytes([*vh, *vl]),
+                u16::from_be_bytes([*bh, *bl]),
+            ),
+            _ => {
+                self.unknown.scroll = true;
+                return;
+            }
+        };
+        if !region.is_valid(gram_height) {
+            self.unknown.scroll = true;
+            return;
+        }
+
+        let start = self.scroll.start_address();
+        self.scroll.region = region;
+        if !self.unknown.scroll {
+            self.sync_scroll_start(start);
+        }
+    }
+
+    /// Updates the scroll offset from a VSCSAD start address.
+    fn sync_scroll_start(&mut self, start: u16) {
+        let ScrollRegion { top_fixed, scroll_area, .. } = self.scroll.region;
+        match start.checked_sub(top_fixed) {
+            Some(offset) if offset < scroll_area => {
+                self.scroll.offset = offset;
+                self.unknown.scroll = false;
+            }
+            _ => self.unknown.scroll = true,
+        }
+    }
+
+    /// Updates a frame rate from FRMCTR1-3 parameters.
+    fn sync_frame_rate(&mut self, command: Command, params: &[u8]) {
+        let rate = match (command, params) {
+            (Command::FRMCTR1, [first, rtn, ..]) => FrameRate::new(ClockDivider::from_bits(*first), first >> 4, *rtn),
+            (_, [_, rtn, ..]) => FrameRate::line_period(*rtn),
+            _ => None,
+        };
+
+        // RTN past 0x1F does not decode, keep the cached rate and flag it
+        let rate = match rate {
+            Some(rate) => rate,
+            None => {
+                self.unknown.frame_rates = true;
+                return;
+            }
+        };
+
+        match command {
+            Command::FRMCTR1 => self.frame_rates.normal = rate,
+            Command::FRMCTR2 => self.frame_rates.idle = rate,
+            _ => self.frame_rates.partial = rate,
+        }
+        self.unknown.frame_rates = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::mock::ready_display;
+    use crate::Rotation;
+
+    #[test]
+    fn send_command_writes_bytes_as_given() {
+        let mut display = ready_display();
+        display.send_command(0xC5, &[0x1C]).unwrap();
+
+        assert_eq!(display.di.commands, [(0xC5, std::vec![0x1C])]);
+        assert!(!display.unknown_state().any());
+    }
+
+    #[test]
+    fn state_changing_commands_are_refused() {
+        let mut display = ready_display();
+        for command in [Command::SWRESET, Command::SLPIN, Command::SLPOUT, Command::DISPOFF] {
+            assert!(matches!(display.send_command(command, &[]), Err(Error::InvalidConfig)));
+        }
+
+        assert!(display.di.commands.is_empty());
+        assert_eq!(display.power_state(), PowerState { sleeping: false, display_on: true });
+    }
+
+    #[test]
+    fn orientation_and_pixel_format_are_decoded() {
+        let mut display = ready_display();
+        display.send_command(Command::MADCTL, &[Madctl::from(Rotation::Deg180).bits()]).unwrap();
+        display.send_command(Command::PIXFMT, &[0x66]).unwrap();
+
+        assert_eq!(display.orientation(), Madctl::from(Rotation::Deg180));
+        assert_eq!(display.pixel_format(), PixelFormat::Rgb666);
+        assert!(!display.unknown_state().any());
+
+        display.send_command(Command::MADCTL, &[]).unwrap();
+        display.send_command(Command::PIXFMT, &[0x00]).unwrap();
+        assert!(display.unknown_state().orientation);
+        assert!(display.unknown_state().pixel_format);
+        assert_eq!(display.pixel_format(), PixelFormat::Rgb666);
+    }
+
+    #[test]
+    fn scroll_region_and_start_are_decoded() {
+        let mut display = ready_display();
+        display.send_command(Command::VSCRDER, &[0, 40, 0x01, 0x90, 0, 40]).unwrap();
+        display.send_command(Command::VSCRSADD, &[0, 50]).unwrap();
+
+        let scroll = display.scroll_state();
+        assert_eq!(scroll.region, ScrollRegion::new(40, 400, 40));
+        assert_eq!(scroll.offset, 10);
+        assert!(!display.unknown_state().scroll);
+
+        // the start address is in the top fixed area
+        display.send_command(Command::VSCRSADD, &[0, 20]).unwrap();
+        assert!(display.unknown_state().scroll);
+
+        // the areas do not add up to the GRAM height
+        display.send_command(Command::VSCRDER, &[0, 40, 0x01, 0x90, 0, 0]).unwrap();
+        assert_eq!(display.scroll_state().region, ScrollRegion::new(40, 400, 40));
+        assert!(display.unknown_state().scroll);
+    }
+
+    #[test]
+    fn partial_area_is_used_by_ptlon() {
+        let mut display = ready_display();
+        display.send_command(Command::PTLAR, &[0, 10, 0, 99]).unwrap();
+        assert_eq!(display.display_mode(), DisplayMode::Normal);
+
+        display.send_command(Command::PTLON, &[]).unwrap();
+        assert_eq!(display.display_mode(), DisplayMode::Partial { start_row: 10, end_row: 99 });
+
+        display.send_command(Command::PTLAR, &[0, 99, 0, 10]).unwrap();
+        assert!(display.unknown_state().display_mode);
+
+        display.send_command(Command::NORON, &[]).unwrap();
+        assert_eq!(display.display_mode(), DisplayMode::Normal);
+    }
+
+    #[test]
+    fn frame_rates_and_brightness_are_decoded() {
+        let mut display = ready_display();
+        display.send_command(Command::FRMCTR1, &[0x51, 0x0C]).unwrap();
+        display.send_command(Command::FRMCTR2, &[0x00, 0x1F]).unwrap();
+        display.send_command(Command::WRDISBV, &[0x80]).unwrap();
+
+        let rates = display.frame_rates();
+        assert_eq!(rates.normal, FrameRate::new(ClockDivider::Fosc2, 0x05, 0x0C).unwrap());
+        assert_eq!(rates.idle, FrameRate::line_period(0x1F).unwrap());
+        assert_eq!(display.cache.brightness, 0x80);
+        assert!(!display.unknown_state().frame_rates);
+    }
+
+    #[test]
+    fn undecodable_frame_rates_are_flagged_until_rewritten() {
+        let mut display = ready_display();
+        let rates = display.frame_rates();
+        display.send_command(Command::FRMCTR3, &[0x00]).unwrap();
+        assert!(display.unknown_state().frame_rates);
+
+        display.send_command(Command::FRMCTR1, &[0xA0, 0x3F]).unwrap();
+        display.send_command(Command::FRMCTR2, &[0x00, 0x20]).unwrap();
+        assert!(display.unknown_state().frame_rates);
+        assert_eq!(display.frame_rates(), rates);
+
+        display.send_command(Command::FRMCTR3, &[0x00, 0x08]).unwrap();
+        assert!(!display.unknown_state().frame_rates);
+        assert_eq!(display.frame_rates().partial, FrameRate::line_period(0x08).unwrap());
+    }
+
+    #[test]
+    fn idle_and_inversion_are_tracked() {
+        let mut display = ready_display();
+        display.send_command(Command::IDMON, &[]).unwrap();
+        display.send_command(Command::INVON, &[]).unwrap();
+
+        assert!(display.idle_mode());
+        assert!(display.is_inverted());
+    }
+}
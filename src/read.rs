@@ -0,0 +1,115 @@
+//! Reading back from the display, for interfaces that can do it.
+
+use crate::instruction::Command;
+use crate::{pixel_count, rgb888_to_rgb565, Backlight, Error, ST7796};
+use display_interface::{DisplayError, WriteOnlyDataCommand};
+use embedded_hal::digital::v2::OutputPin;
+
+/// Number of pixels read per RAMRD / RDMEMC transfer.
+const READ_CHUNK: usize = 32;
+
+///
+/// Display interface that can also read parameters back from the display.
+///
+pub trait ReadWriteDataCommand: WriteOnlyDataCommand {
+    ///
+    /// Sends `command` and fills `buf` with the parameters the display returns.
+    ///
+    /// The parameters must be returned as listed in the datasheet command
+    /// table, including the leading dummy parameter. Serial implementations,
+    /// which see a dummy clock cycle or no dummy at all, put a placeholder
+    /// byte first.
+    ///
+    fn read_command(&mut self, command: u8, buf: &mut [u8]) -> Result<(), DisplayError>;
+}
+
+impl<DI, RST, BL, RstE, BlE> ST7796<DI, RST, BL>
+where
+    DI: ReadWriteDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    ///
+    /// Reads pixel colors from the frame memory in given rectangle bounds.
+    ///
+    /// The display returns 18 bit pixels regardless of the pixel format,
+    /// they are repacked to Rgb565.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `colors` - buffer filled with Rgb565 colors, row by row. At most the
+    ///   pixels of the rectangle are read, remaining entries are left untouched.
+    ///
+    pub fn read_pixels(
+        &mut self,
+        sx: u16, sy: u16,
+        ex: u16, ey: u16,
+        colors: &mut [u16],
+    ) -> Result<(), Error<RstE, BlE>> {
+        let len = colors.len().min(pixel_count(sx, sy, ex, ey));
+        self.set_address_window(sx, sy, ex, ey)?;
+
+        // 1 dummy byte followed by 3 bytes per pixel
+        let mut buf = [0u8; 1 + 3 * READ_CHUNK];
+        let mut command = Command::RAMRD;
+        for chunk in colors[..len].chunks_mut(READ_CHUNK) {
+            let bytes = &mut buf[..1 + 3 * chunk.len()];
+            self.read_command(command, bytes)?;
+            for (color, rgb) in chunk.iter_mut().zip(bytes[1..].chunks_exact(3)) {
+                // 6 bits per byte, MSB aligned: reduces like a 24 bit color
+                *color = rgb888_to_rgb565(u32::from_be_bytes([0, rgb[0], rgb[1], rgb[2]]));
+            }
+            command = Command::RDMEMC;
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, RST, BL, S, RstE, BlE> ST7796<DI, RST, BL, S>
+where
+    DI: ReadWriteDataCommand,
+    RST: OutputPin<Error = RstE>,
+    BL: Backlight<Error = BlE>,
+{
+    /// Private method:Reads the parameters of `command` into `buf`.
+    pub(crate) fn read_command(&mut self, command: Command, buf: &mut [u8]) -> Result<(), Error<RstE, BlE>> {
+        self.di
+            .read_command(command as u8, buf)
+            .map_err(Error::Interface)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::instruction::Command;
+    use crate::mock::ready_display;
+    use std::vec::Vec;
+
+    #[test]
+    fn pixels_are_read_in_chunks_and_repacked() {
+        let mut display = ready_display();
+        // 10x4 pixels: a RAMRD of 32 pixels, then a RDMEMC of 8
+        for chunk in [0..32, 32..40] {
+            display.di.read_data.push(0xAA); // dummy
+            for i in chunk {
+                display.di.read_data.extend([(i as u8) << 2, 0x80, 0xFC]);
+            }
+        }
+        let mut colors = [0u16; 41];
+        display.read_pixels(0, 0, 9, 3, &mut colors).unwrap();
+
+        let expected: Vec<u16> = (0..40u16).map(|i| (i >> 1) << 11 | 0x20 << 5 | 0x1F).collect();
+        assert_eq!(colors[..40], expected[..]);
+        assert_eq!(colors[40], 0);
+        assert!(display.di.read_data.is_empty());
+        let commands = [Command::CASET, Command::RASET, Command::RAMRD, Command::RDMEMC];
+        assert_eq!(display.di.command_bytes(), commands.map(|command| command as u8));
+    }
+}
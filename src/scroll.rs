@@ -0,0 +1,150 @@
+//! Vertical scrolling: area definition (VSCRDEF, 33h) and start address (VSCSAD, 37h).
+
+use crate::instruction::{Command, Instruction};
+
+///
+/// Vertical scrolling areas, in frame memory lines.
+///
+/// The three areas must add up to the GRAM height (480 lines).
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ScrollRegion {
+    /// Lines fixed at the top of the display (TFA).
+    pub top_fixed: u16,
+    /// Lines that scroll (VSA).
+    pub scroll_area: u16,
+    /// Lines fixed at the bottom of the display (BFA).
+    pub bottom_fixed: u16,
+}
+
+impl ScrollRegion {
+    ///
+    /// Creates a scroll region from its three areas.
+    ///
+    pub const fn new(top_fixed: u16, scroll_area: u16, bottom_fixed: u16) -> Self {
+        Self {
+            top_fixed,
+            scroll_area,
+            bottom_fixed,
+        }
+    }
+
+    ///
+    /// Creates a region where all `gram_height` lines scroll.
+    ///
+    pub const fn full(gram_height: u16) -> Self {
+        Self::new(0, gram_height, 0)
+    }
+
+    ///
+    /// Returns true if the scrolling area is not empty and the areas add up to `gram_height`.
+    ///
+    pub fn is_valid(&self, gram_height: u16) -> bool {
+        let total = u32::from(self.top_fixed) + u32::from(self.scroll_area) + u32::from(self.bottom_fixed);
+        self.scroll_area > 0 && total == u32::from(gram_height)
+    }
+}
+
+impl Instruction for ScrollRegion {
+    const COMMAND: Command = Command::VSCRDER;
+    type Bytes = [u8; 6];
+
+    fn to_bytes(&self) -> [u8; 6] {
+        let [tfa_h, tfa_l] = self.top_fixed.to_be_bytes();
+        let [vsa_h, vsa_l] = self.scroll_area.to_be_bytes();
+        let [bfa_h, bfa_l] = self.bottom_fixed.to_be_bytes();
+
+        [tfa_h, tfa_l, vsa_h, vsa_l, bfa_h, bfa_l]
+    }
+}
+
+impl Default for ScrollRegion {
+    fn default() -> Self {
+        Self::full(480)
+    }
+}
+
+///
+/// Current vertical scroll setting: the region and the offset into its scrolling area.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScrollState {
+    /// Scroll areas as sent with VSCRDEF.
+    pub region: ScrollRegion,
+    /// Lines the scrolling area is shifted by, `0..region.scroll_area`.
+    pub offset: u16,
+}
+
+impl ScrollState {
+    ///
+    /// Returns the VSCSAD start address for the current offset.
+    ///
+    pub fn start_address(&self) -> u16 {
+        self.region.top_fixed + self.offset
+    }
+
+    ///
+    /// Returns the frame memory line shown on display line `row`.
+    /// Lines in the fixed areas map onto themselves.
+    ///
+    pub fn memory_row(&self, row: u16) -> u16 {
+        let ScrollRegion { top_fixed, scroll_area, .. } = self.region;
+        if row < top_fixed || row - top_fixed >= scroll_area {
+            return row;
+        }
+
+        top_fixed + (row - top_fixed + self.offset) % scroll_area
+    }
+
+    ///
+    /// Returns the display line showing frame memory line `row`, the inverse
+    /// of [`ScrollState::memory_row`].
+    ///
+    pub fn display_row(&self, row: u16) -> u16 {
+        let ScrollRegion { top_fixed, scroll_area, .. } = self.region;
+        if row < top_fixed || row - top_fixed >= scroll_area {
+            return row;
+        }
+
+        top_fixed + (row - top_fixed + scroll_area - self.offset) % scroll_area
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_round_trip() {
+        let region = ScrollRegion::new(40, 400, 40);
+        for offset in [0, 1, 199, 399] {
+            let scroll = ScrollState { region, offset };
+            for row in 0..480 {
+                assert_eq!(scroll.display_row(scroll.memory_row(row)), row);
+                assert_eq!(scroll.memory_row(scroll.display_row(row)), row);
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_areas_do_not_scroll() {
+        let scroll = ScrollState { region: ScrollRegion::new(40, 400, 40), offset: 10 };
+
+        assert_eq!(scroll.memory_row(0), 0);
+        assert_eq!(scroll.memory_row(39), 39);
+        assert_eq!(scroll.memory_row(440), 440);
+        assert_eq!(scroll.memory_row(40), 50);
+        assert_eq!(scroll.memory_row(439), 49);
+        assert_eq!(scroll.start_address(), 50);
+    }
+
+    #[test]
+    fn region_must_cover_gram() {
+        assert!(ScrollRegion::default().is_valid(480));
+        assert!(ScrollRegion::new(40, 400, 40).is_valid(480));
+        assert!(!ScrollRegion::new(40, 400, 39).is_valid(480));
+        assert!(!ScrollRegion::new(240, 0, 240).is_valid(480));
+        assert!(!ScrollRegion::new(u16::MAX, u16::MAX, 2).is_valid(480));
+        assert_eq!(ScrollRegion::new(40, 400, 40).to_bytes(), [0, 40, 0x01, 0x90, 0, 40]);
+    }
+}
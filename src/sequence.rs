@@ -0,0 +1,214 @@
+//! Command sequences shared by the blocking and async drivers.
+//!
+//! The drivers only differ in how they send a command and wait, so the
+//! commands, their parameters and the waits between them are built here
+//! and each driver runs them step by step.
+
+use crate::instruction::{ColumnAddress, Command, Instruction, RowAddress, ScrollStartAddress};
+use crate::raw::CachedState;
+use crate::{
+    DrivingCurrents, DrivingVoltages, FrameRates, Gamma, IdleFrameRate, InitConfig, NegativeGamma, NormalFrameRate,
+    PartialFrameRate, PositiveGamma, PowerSettings, ScrollState, Vcom, VcomOffset, Vrh, SLEEP_COMMAND_DELAY_US,
+    SLEEP_OUT_TO_SLEEP_IN_US, SOFT_RESET_TO_SLEEP_OUT_US,
+};
+use core::iter::once;
+
+/// Most parameter bytes of a typed register (the gamma curves).
+const MAX_PARAMS: usize = 14;
+
+/// Enables Command Table 2 part I and II.
+const CSCON_ENABLE: [(Command, Params<'static>); 2] = [
+    (Command::CSCON, Params::Borrowed(&[0xC3])),
+    (Command::CSCON, Params::Borrowed(&[0x96])),
+];
+
+/// Disables Command Table 2 part I and II.
+const CSCON_DISABLE: [(Command, Params<'static>); 2] = [
+    (Command::CSCON, Params::Borrowed(&[0x3C])),
+    (Command::CSCON, Params::Borrowed(&[0x69])),
+];
+
+///
+/// Parameter bytes of one command.
+///
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum Params<'a> {
+    /// Encoded from a typed register.
+    Inline([u8; MAX_PARAMS], usize),
+    /// Given by the caller, such as the commands of an [`InitConfig`].
+    Borrowed(&'a [u8]),
+}
+
+impl Params<'_> {
+    /// Returns the encoded parameters of `register`.
+    pub(crate) fn of<I: Instruction>(register: &I) -> Self {
+        let bytes = register.to_bytes();
+        let bytes = bytes.as_ref();
+        let mut inline = [0; MAX_PARAMS];
+        inline[..bytes.len()].copy_from_slice(bytes);
+
+        Params::Inline(inline, bytes.len())
+    }
+
+    /// Returns the bytes to send.
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        match self {
+            Params::Inline(bytes, len) => &bytes[..*len],
+            Params::Borrowed(bytes) => bytes,
+        }
+    }
+}
+
+///
+/// One step of a sequence with waits.
+///
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum Step<'a> {
+    /// Sends a command with its parameters.
+    Write(Command, Params<'a>),
+    /// Waits, in microseconds.
+    Wait(u32),
+}
+
+impl<'a> From<(Command, Params<'a>)> for Step<'a> {
+    fn from((command, params): (Command, Params<'a>)) -> Self {
+        Step::Write(command, params)
+    }
+}
+
+/// Returns `command` without parameters.
+fn command(command: Command) -> (Command, Params<'static>) {
+    (command, Params::Borrowed(&[]))
+}
+
+/// Returns `writes`, enabled with CSCON for the duration if `command2` is set.
+fn command2<'a>(
+    writes: impl IntoIterator<Item = (Command, Params<'a>)>,
+    command2: bool,
+) -> impl Iterator<Item = (Command, Params<'a>)> {
+    let (enable, disable): (&[_], &[_]) = if command2 { (&CSCON_ENABLE, &CSCON_DISABLE) } else { (&[], &[]) };
+
+    enable.iter().copied().chain(writes).chain(disable.iter().copied())
+}
+
+/// Returns the writes of a typed register, enabled with CSCON when it needs it.
+pub(crate) fn register<I: Instruction>(register: &I) -> impl Iterator<Item = (Command, Params<'static>)> {
+    command2(once((I::COMMAND, Params::of(register))), I::COMMAND.is_command2())
+}
+
+/// Returns the writes of a raw command, enabled with CSCON when it needs it.
+pub(crate) fn raw(command: Command, params: &[u8]) -> impl Iterator<Item = (Command, Params<'_>)> {
+    command2(once((command, Params::Borrowed(params))), command.is_command2())
+}
+
+/// Returns the writes of both gamma curves.
+pub(crate) fn gamma(gamma: &Gamma) -> impl Iterator<Item = (Command, Params<'static>)> {
+    let positive = PositiveGamma(gamma.positive);
+    let negative = NegativeGamma(gamma.negative);
+
+    command2([(PositiveGamma::COMMAND, Params::of(&positive)), (NegativeGamma::COMMAND, Params::of(&negative))], true)
+}
+
+/// Returns the writes of all power control registers.
+pub(crate) fn power(power: &PowerSettings) -> impl Iterator<Item = (Command, Params<'static>)> {
+    command2(
+        [
+            (DrivingVoltages::COMMAND, Params::of(&power.driving_voltages)),
+            (Vrh::COMMAND, Params::of(&power.vrh)),
+            (DrivingCurrents::COMMAND, Params::of(&power.driving_currents)),
+            (Vcom::COMMAND, Params::of(&power.vcom)),
+            (VcomOffset::COMMAND, Params::of(&power.vcom_offset)),
+        ],
+        true,
+    )
+}
+
+/// Returns the writes of the normal, idle and partial frame rates.
+pub(crate) fn frame_rates(frame_rates: &FrameRates) -> impl Iterator<Item = (Command, Params<'static>)> {
+    command2(
+        [
+            (NormalFrameRate::COMMAND, Params::of(&NormalFrameRate(frame_rates.normal))),
+            (IdleFrameRate::COMMAND, Params::of(&IdleFrameRate(frame_rates.idle))),
+            (PartialFrameRate::COMMAND, Params::of(&PartialFrameRate(frame_rates.partial))),
+        ],
+        true,
+    )
+}
+
+///
+/// Returns the bring-up sequence described by `config`, from SWRESET to
+/// DISPON. `hard_reset` tells if the display was just reset with its pin.
+///
+/// Without a hard reset the display may still be running a sleep out
+/// sequence, which SWRESET must not interrupt, and SWRESET leaves it
+/// sleeping, so SLPOUT has to wait as long as after a hard reset.
+///
+pub(crate) fn init(config: &InitConfig, hard_reset: bool) -> impl Iterator<Item = Step<'static>> + '_ {
+    let (sleep_out_wait, reset_delay_us) = if hard_reset {
+        (None, config.reset_delay_us)
+    } else {
+        (Some(Step::Wait(SLEEP_OUT_TO_SLEEP_IN_US)), config.reset_delay_us.max(SOFT_RESET_TO_SLEEP_OUT_US))
+    };
+    let scroll = ScrollState { region: config.scroll_region, offset: 0 };
+    let inversion = if config.inverted { Command::INVON } else { Command::INVOFF };
+
+    let writes = register(&config.scroll_region)
+        .chain(register(&ScrollStartAddress(scroll.start_address())))
+        .chain(register(&config.madctl))
+        .chain(register(&config.pixel_format))
+        .chain(config.power.iter().flat_map(power))
+        .chain(config.gamma.iter().flat_map(gamma))
+        .chain(config.display_function.iter().flat_map(register))
+        .chain(config.commands.iter().flat_map(|&(command, params)| raw(command, params)));
+
+    sleep_out_wait
+        .into_iter()
+        .chain([
+            command(Command::SWRESET).into(),
+            Step::Wait(reset_delay_us),
+            command(Command::SLPOUT).into(),
+            Step::Wait(config.sleep_out_delay_us.max(SLEEP_COMMAND_DELAY_US)),
+        ])
+        .chain(writes.map(Step::from))
+        .chain([
+            command(inversion).into(),
+            Step::Wait(10_000),
+            command(Command::NORON).into(),
+            Step::Wait(10_000),
+            command(Command::DISPON).into(),
+            Step::Wait(10_000),
+        ])
+}
+
+///
+/// Returns the writes setting the address window to the rectangle bounds,
+/// shifted by the panel offset, or `None` unless they are inside the visible area.
+///
+pub(crate) fn address_window(
+    state: &CachedState,
+    sx: u16, sy: u16,
+    ex: u16, ey: u16,
+) -> Option<impl Iterator<Item = (Command, Params<'static>)>> {
+    let (width, height) = state.geometry.size(state.madctl);
+    if sx > ex || sy > ey || ex >= width || ey >= height {
+        return None;
+    }
+
+    let (ox, oy) = state.geometry.offset(state.madctl);
+    let columns = ColumnAddress { start: sx + ox, end: ex + ox };
+    let rows = RowAddress { start: sy + oy, end: ey + oy };
+
+    Some(register(&columns).chain(register(&rows)))
+}
+
+/// Returns SLPIN, after waiting `wait_us` for the display to accept it.
+pub(crate) fn sleep(wait_us: u32) -> impl Iterator<Item = Step<'static>> {
+    let wait = (wait_us > 0).then_some(Step::Wait(wait_us));
+
+    wait.into_iter().chain([command(Command::SLPIN).into(), Step::Wait(SLEEP_COMMAND_DELAY_US)])
+}
+
+/// Returns SLPOUT; the rest of the sleep out sequence is waited before SLPIN.
+pub(crate) fn wake() -> impl Iterator<Item = Step<'static>> {
+    [command(Command::SLPOUT).into(), Step::Wait(SLEEP_COMMAND_DELAY_US)].into_iter()
+}
@@ -0,0 +1,24 @@
+//! Driver states, carried by `ST7796` as a type parameter.
+//!
+//! `ST7796::new` returns an [`Uninit`] driver, `init` turns it into a
+//! [`Ready`] one and `sleep` / `wake` move between [`Ready`] and [`Sleeping`].
+//! Drawing is only available while [`Ready`]. When one of these fails the
+//! driver is handed back in its previous state along with the error.
+
+///
+/// Created but not initialized: only pins, reset and `init` are available.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Uninit;
+
+///
+/// Initialized and awake: commands and drawing are available.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Ready;
+
+///
+/// In sleep mode (SLPIN): the panel is not driven until `wake`.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Sleeping;